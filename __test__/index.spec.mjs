@@ -1,6 +1,33 @@
 import test from 'ava'
 import fs from 'fs'
-import { parseForm13F, parseForm13FTable, parseOwnershipForm, parseXbrl } from '../index.js'
+import {
+  aggregateForm13FTable,
+  applyForm13FAmendment,
+  applyOwnershipAmendment,
+  attributeOwners,
+  buildPositionLedger,
+  cusipToIsin,
+  describeTransactionCode,
+  diffForm13FTables,
+  enrichForm13FTable,
+  flattenOwnershipForm,
+  isForm13FAmendment,
+  issuerCusip,
+  normalizeForm13FValues,
+  ownershipRowsToCsv,
+  parseForm13F,
+  parseForm13FTable,
+  parseForm144,
+  parseOwnershipForm,
+  parseSchedule13D,
+  parseSchedule13G,
+  parseTickerCsv,
+  parseXbrl,
+  summarizeInsiderActivity,
+  validateCusip,
+  validateFigi,
+  validateForm13FTable,
+} from '../index.js'
 
 test('parse 8k from native', async (t) => {
   const startTime = Date.now()
@@ -76,6 +103,462 @@ test('parse 10q from native', async (t) => {
   t.is(period.end_date, undefined)
 })
 
+test('parse xbrl contexts from native', async (t) => {
+  const file = fs.readFileSync('./__test__/data/docxbrl-contexts.xml', 'utf8')
+  const result = parseXbrl(file)
+
+  t.is(result.facts.length, 4)
+
+  const [first, second, third, fourth] = result.facts.map((fact) => fact.context)
+  t.is(first.entityScheme, 'http://www.sec.gov/CIK')
+  t.deepEqual(first.segments, [
+    { dimension: 'StatementBusinessSegmentsAxis', member: 'WidgetsMember' },
+    { dimension: 'ProductOrServiceAxis', member: 'GadgetsMember' },
+  ])
+  t.deepEqual(first.segmentContent, ['<acme:Region>North America</acme:Region>'])
+  t.deepEqual(first.scenarios, [{ dimension: 'ForecastAxis', member: 'Budget' }])
+  t.deepEqual(first.scenarioContent, ['<acme:Restated>true</acme:Restated>'])
+
+  const dimensionsKey =
+    'http://www.sec.gov/CIK#0000123456|2023-01-01--2023-12-31|ForecastAxis=Budget,ProductOrServiceAxis=GadgetsMember,StatementBusinessSegmentsAxis=WidgetsMember'
+  t.is(
+    first.key,
+    `${dimensionsKey}|scenario:<acme:Restated>true</acme:Restated>,segment:<acme:Region>North America</acme:Region>`
+  )
+  t.is(second.key, dimensionsKey)
+  t.is(fourth.key, first.key)
+  t.is(third.key, 'http://www.sec.gov/CIK#0000123456|2023-12-31|')
+})
+
+test('parse malformed xbrl contexts from native', async (t) => {
+  const file = fs.readFileSync('./__test__/data/docxbrl-malformed.xml', 'utf8')
+
+  t.throws(() => parseXbrl(file, { mode: 'Strict' }), { message: 'xbrl/context[2]/@id not found' })
+
+  const result = parseXbrl(file, { mode: 'Lenient' })
+  t.is(result.facts.length, 1)
+  t.is(result.facts[0].concept, 'Assets')
+  t.deepEqual(result.warnings, [
+    {
+      code: 'MISSING_ELEMENT',
+      path: 'xbrl/context[2]/@id',
+      message: 'xbrl/context[2]/@id not found',
+      line: 16,
+      column: 5,
+    },
+    {
+      code: 'MISSING_ELEMENT',
+      path: 'xbrl/context[3]/entity/segment/explicitMember/@dimension',
+      message: 'xbrl/context[3]/entity/segment/explicitMember/@dimension not found',
+      line: 28,
+      column: 17,
+    },
+  ])
+
+  const noPeriod = file.replace(/<period>\s*<instant>2023-12-31<\/instant>\s*<\/period>/, '')
+  const lenient = parseXbrl(noPeriod, { mode: 'Lenient' })
+  t.is(lenient.facts.length, 0)
+  t.is(lenient.warnings[0].code, 'MISSING_ELEMENT')
+  t.is(lenient.warnings[0].path, 'xbrl/context[1]/period')
+  t.is(lenient.warnings.length, 3)
+})
+
+test('parse errors from native', async (t) => {
+  const form4 = fs.readFileSync('./__test__/data/doc4.xml', 'utf8')
+  const missing = t.throws(() =>
+    parseOwnershipForm(form4.replace('<issuerCik>1212121212</issuerCik>', ''), { mode: 'Strict' })
+  )
+  t.is(missing.code, 'MISSING_ELEMENT')
+  t.is(missing.path, 'ownershipDocument/issuer/issuerCik')
+  t.is(missing.line, 11)
+  t.is(missing.column, 5)
+
+  for (const [parent, path] of [
+    ['</issuer>', 'ownershipDocument/issuer/foo'],
+    [
+      '</reportingOwnerRelationship>',
+      'ownershipDocument/reportingOwner[1]/reportingOwnerRelationship/foo',
+    ],
+    [
+      '</transactionAmounts>',
+      'ownershipDocument/nonDerivativeTable/nonDerivativeTransaction[1]/transactionAmounts/foo',
+    ],
+    [
+      '</ownershipNature>',
+      'ownershipDocument/nonDerivativeTable/nonDerivativeTransaction[1]/ownershipNature/foo',
+    ],
+  ]) {
+    const unknown = t.throws(() =>
+      parseOwnershipForm(form4.replace(parent, `<foo>1</foo>${parent}`), { mode: 'Strict' })
+    )
+    t.is(unknown.code, 'UNKNOWN_ELEMENT')
+    t.is(unknown.path, path)
+  }
+
+  const syntax = t.throws(() => parseOwnershipForm(form4.replace('</issuer>', '')))
+  t.is(syntax.code, 'XML_SYNTAX')
+  t.is(syntax.path, undefined)
+  t.true(syntax.line > 0)
+
+  const form13F = fs.readFileSync('./__test__/data/doc13f-hr.xml', 'utf8')
+  const invalid = t.throws(() =>
+    parseForm13F(form13F.replace('<tableEntryTotal>111<', '<tableEntryTotal>many<'), {
+      mode: 'Strict',
+    })
+  )
+  t.is(invalid.code, 'INVALID_VALUE')
+  t.is(invalid.path, 'edgarSubmission/formData/summaryPage/tableEntryTotal')
+})
+
+test('parse warnings from native', async (t) => {
+  const form4 = fs
+    .readFileSync('./__test__/data/doc4.xml', 'utf8')
+    .replace('<isDirector>1<', '<isDirector>maybe<')
+  t.is(t.throws(() => parseOwnershipForm(form4, { mode: 'Strict' })).code, 'INVALID_VALUE')
+  const ownership = parseOwnershipForm(form4, { mode: 'Lenient' })
+  t.is(ownership.reportingOwners[0].relationship.isDirector, undefined)
+  t.is(ownership.warnings.length, 1)
+  t.is(ownership.warnings[0].code, 'INVALID_VALUE')
+  t.is(
+    ownership.warnings[0].path,
+    'ownershipDocument/reportingOwner[1]/reportingOwnerRelationship/isDirector'
+  )
+
+  const noSymbol = form4.replace(/<issuerTradingSymbol>.*<\/issuerTradingSymbol>/, '')
+  t.is(t.throws(() => parseOwnershipForm(noSymbol, { mode: 'Strict' })).code, 'MISSING_ELEMENT')
+  const lenientNoSymbol = parseOwnershipForm(noSymbol, { mode: 'Lenient' })
+  t.is(lenientNoSymbol.issuer.tradingSymbol, '')
+  t.deepEqual(
+    lenientNoSymbol.warnings.map((warning) => [warning.code, warning.path]),
+    [
+      ['MISSING_ELEMENT', 'ownershipDocument/issuer/issuerTradingSymbol'],
+      [
+        'INVALID_VALUE',
+        'ownershipDocument/reportingOwner[1]/reportingOwnerRelationship/isDirector',
+      ],
+    ]
+  )
+
+  const nested = parseOwnershipForm(
+    fs
+      .readFileSync('./__test__/data/doc4.xml', 'utf8')
+      .replace('</issuer>', '<bar>1</bar></issuer>')
+      .replace('</transactionAmounts>', '<foo>1</foo></transactionAmounts>')
+      .replace('</underlyingSecurity>', '<baz>1</baz></underlyingSecurity>')
+  )
+  t.is(nested.issuer.cik, '1212121212')
+  t.is(nested.nonDerivativeTable.transactions.length, 2)
+  t.deepEqual(
+    nested.warnings.map((warning) => [warning.code, warning.path]),
+    [
+      ['UNKNOWN_ELEMENT', 'ownershipDocument/issuer/bar'],
+      [
+        'UNKNOWN_ELEMENT',
+        'ownershipDocument/nonDerivativeTable/nonDerivativeTransaction[1]/transactionAmounts/foo',
+      ],
+      [
+        'UNKNOWN_ELEMENT',
+        'ownershipDocument/derivativeTable/derivativeHolding/underlyingSecurity/baz',
+      ],
+    ]
+  )
+
+  const table = fs
+    .readFileSync('./__test__/data/doc13f-table.xml', 'utf8')
+    .replace('<ns1:cusip>02079K107</ns1:cusip>', '')
+    .replace('<ns1:figi>02079K101453</ns1:figi>', '<ns1:ticker>ADBE</ns1:ticker>')
+  t.is(
+    t.throws(() => parseForm13FTable(table, { mode: 'Strict' })).path,
+    'informationTable/infoTable[1]/cusip'
+  )
+  const result = parseForm13FTable(table, { mode: 'Lenient' })
+  t.is(result.entries.length, 168)
+  t.is(parseForm13FTable(table).entries.length, 168)
+  t.deepEqual(
+    result.warnings.map((warning) => [warning.code, warning.path]),
+    [
+      ['SKIPPED_ROW', 'informationTable/infoTable[1]'],
+      ['UNKNOWN_ELEMENT', 'informationTable/infoTable[2]/ticker'],
+    ]
+  )
+})
+
+test('resolve ownership footnotes from native', async (t) => {
+  const form4 = fs.readFileSync('./__test__/data/doc4.xml', 'utf8')
+  const result = parseOwnershipForm(form4, { resolveFootnotes: true })
+  const transaction = result.nonDerivativeTable.transactions[0]
+  t.deepEqual(transaction.transactionCoding.footnotes, [
+    'Footnote 1.',
+    'Footnote 2.',
+    'Footnote 3.',
+  ])
+  t.deepEqual(transaction.transactionTimeliness.footnotes, ['Footnote 3.'])
+  t.deepEqual(result.derivativeTable.holdings[0].conversionOrExercisePrice.footnotes, [
+    'Footnote 5.',
+  ])
+  t.true(
+    result.footnotes[1].references.includes(
+      'ownershipDocument/nonDerivativeTable/nonDerivativeTransaction[1]/transactionCoding'
+    )
+  )
+  t.is(parseOwnershipForm(form4).footnotes[1].references, undefined)
+
+  const dangling = form4.replace('<footnoteId id="F2"/>', '<footnoteId id="F9"/>')
+  t.is(
+    t.throws(() => parseOwnershipForm(dangling, { mode: 'Strict', resolveFootnotes: true })).code,
+    'INVALID_VALUE'
+  )
+  const lenient = parseOwnershipForm(dangling, { mode: 'Lenient', resolveFootnotes: true })
+  t.deepEqual(lenient.nonDerivativeTable.transactions[0].transactionCoding.footnotes, [
+    'Footnote 1.',
+    'Footnote 3.',
+  ])
+  t.is(lenient.warnings.length, 1)
+})
+
+test('parse transaction codes from native', async (t) => {
+  const form4 = fs.readFileSync('./__test__/data/doc4.xml', 'utf8')
+  const transaction = parseOwnershipForm(form4).nonDerivativeTable.transactions[0]
+  t.is(transaction.transactionCoding.transactionCode, 'J')
+  t.is(transaction.transactionAmounts.acquiredDisposedCode.value, 'A')
+  t.is(transaction.ownershipNature.directOrIndirectOwnership.value, 'I')
+  t.is(
+    describeTransactionCode('P'),
+    'Open market or private purchase of non-derivative or derivative security'
+  )
+  t.is(describeTransactionCode('Q'), null)
+
+  const unknownCode = form4.replace('<transactionCode>J<', '<transactionCode>Q<')
+  t.is(t.throws(() => parseOwnershipForm(unknownCode, { mode: 'Strict' })).code, 'INVALID_VALUE')
+  const lenient = parseOwnershipForm(unknownCode, { mode: 'Lenient' })
+  t.is(lenient.nonDerivativeTable.transactions[0].transactionCoding.transactionCode, undefined)
+  t.true(lenient.warnings[0].message.includes('unknown TransactionCode code: Q'))
+})
+
+test('parse typed ownership values from native', async (t) => {
+  const form4 = fs
+    .readFileSync('./__test__/data/doc4.xml', 'utf8')
+    .replace('<value>Common Stock</value>', '<value>1</value>')
+    .replace(
+      /<transactionPricePerShare>\s*<value>0<\/value>/,
+      '<transactionPricePerShare><footnoteId id="F2"/>'
+    )
+  const transaction = parseOwnershipForm(form4).nonDerivativeTable.transactions[0]
+  t.is(transaction.securityTitle.value, '1')
+  t.is(transaction.transactionDate.value, '2002-11-01')
+  t.deepEqual(transaction.transactionAmounts.pricePerShare, { footnoteIds: ['F2'] })
+
+  const badDate = form4.replace('<value>2002-11-01</value>', '<value>11/01/2002</value>')
+  t.is(t.throws(() => parseOwnershipForm(badDate, { mode: 'Strict' })).code, 'INVALID_VALUE')
+})
+
+test('summarize insider activity from native', async (t) => {
+  const form4 = fs
+    .readFileSync('./__test__/data/doc4.xml', 'utf8')
+    .replace('<transactionCode>J<', '<transactionCode>P<')
+    .replace('<transactionCode>C<', '<transactionCode>M<')
+    .replace(/<transactionPricePerShare>\s*<value>0</, '<transactionPricePerShare><value>12.5<')
+    .replace(/<transactionPricePerShare>\s*<value>0</, '<transactionPricePerShare><value>10<')
+  const summaries = summarizeInsiderActivity(parseOwnershipForm(form4))
+  t.is(summaries.length, 2)
+  t.deepEqual(summaries[1], {
+    ownerCik: '1212121212',
+    sharesPurchased: 2000,
+    sharesSold: 33333,
+    netShares: -31333,
+    purchaseValue: 25000,
+    saleValue: 333330,
+    totalValue: 358330,
+    averagePurchasePrice: 12.5,
+    averageSalePrice: 10,
+    sharesOwnedFollowing: [
+      { securityTitle: 'Common Stock', directOrIndirect: 'I', shares: 999 },
+      { securityTitle: 'Preferred Stock Options', directOrIndirect: 'D', shares: 33333 },
+    ],
+    sharesExercised: 0,
+    optionExercises: 1,
+    openMarketSales: 1,
+    aff10B5One: true,
+  })
+})
+
+test('apply ownership amendment from native', async (t) => {
+  const original = parseOwnershipForm(fs.readFileSync('./__test__/data/doc4.xml', 'utf8'))
+  const amendedFile = fs
+    .readFileSync('./__test__/data/doc4a.xml', 'utf8')
+    .replace(/<transactionShares>\s*<value>33333</, '<transactionShares><value>30000<')
+    .replace(/<derivativeTransaction>[\s\S]*<\/derivativeTransaction>/, '')
+  const result = applyOwnershipAmendment(original, parseOwnershipForm(amendedFile))
+
+  t.is(result.nonDerivativeTable.transactions[1].transactionAmounts.shares.value, 30000)
+  t.is(result.derivativeTable.transactions.length, 0)
+  t.deepEqual(result.changes, [
+    { kind: 'Changed', table: 'NonDerivativeTransaction', originalIndex: 1, amendedIndex: 1 },
+    { kind: 'Removed', table: 'DerivativeTransaction', originalIndex: 0 },
+  ])
+
+  const renumbered = parseOwnershipForm(amendedFile.replace(/id="F1"/g, 'id="F9"'))
+  t.deepEqual(applyOwnershipAmendment(original, renumbered).changes, result.changes)
+
+  t.is(
+    t.throws(() => applyOwnershipAmendment(original, original)).message,
+    'form 4 is not an amendment'
+  )
+})
+
+test('attribute owners from native', async (t) => {
+  const form = parseOwnershipForm(fs.readFileSync('./__test__/data/doc4.xml', 'utf8'))
+  const result = attributeOwners(form)
+
+  t.is(result.designatedFiler.cik, '0000343434')
+  t.deepEqual(result.directors.map((owner) => owner.cik), ['0000343434', '1212121212'])
+  t.is(result.tenPercentOwners.length, 0)
+  t.is(result.transactions.length, 6)
+
+  const [first, , , fourth] = result.transactions
+  t.is(first.ownerCik, '0000343434')
+  t.true(first.isDesignatedFiler)
+  t.is(first.table, 'NonDerivativeTransaction')
+  t.is(first.transactionCode, 'J')
+  t.is(first.shares, 2000)
+  t.is(fourth.ownerCik, '1212121212')
+  t.false(fourth.isDesignatedFiler)
+  t.is(fourth.index, 0)
+  t.is(fourth.shares, 2000)
+  t.is(result.transactions[5].table, 'DerivativeTransaction')
+})
+
+test('flatten ownership form from native', async (t) => {
+  const form = parseOwnershipForm(fs.readFileSync('./__test__/data/doc4.xml', 'utf8'))
+  const rows = flattenOwnershipForm(form)
+
+  t.is(rows.length, 12)
+  t.deepEqual(
+    rows.slice(0, 6).map((row) => row.table),
+    [
+      'NonDerivativeTransaction',
+      'NonDerivativeTransaction',
+      'NonDerivativeHolding',
+      'NonDerivativeHolding',
+      'DerivativeTransaction',
+      'DerivativeHolding',
+    ]
+  )
+  t.is(rows[0].issuerTradingSymbol, 'AWI')
+  t.is(rows[0].ownerCik, '0000343434')
+  t.true(rows[0].isDesignatedFiler)
+  t.is(rows[6].ownerCik, '1212121212')
+  t.false(rows[6].isDesignatedFiler)
+  t.deepEqual({ ...rows[6], ownerCik: '0000343434', isDesignatedFiler: true }, rows[0])
+  t.is(rows[0].transactionCode, 'J')
+  t.is(rows[0].sharesOwnedFollowing, 999)
+  t.is(rows[0].footnotes, 'F1: Footnote 1.; F2: Footnote 2.; F3: Footnote 3.')
+  t.is(rows[3].natureOfOwnership, 'Owned Indirectly')
+  t.is(rows[3].footnotes, undefined)
+
+  const lines = ownershipRowsToCsv(rows).split('\r\n')
+  t.is(lines.length, 14)
+  t.true(lines[0].startsWith('issuerCik,issuerName,issuerTradingSymbol,ownerCik'))
+  t.is(
+    lines[2],
+    '1212121212,,AWI,0000343434,,true,NonDerivativeTransaction,Preferred Stock Options,2002-11-02,S,33333,0,D,,D,,F1: Footnote 1.'
+  )
+
+  const quoted = ownershipRowsToCsv([{ ...rows[3], securityTitle: 'Class "A", Common' }])
+  t.true(quoted.includes(',"Class ""A"", Common",'))
+})
+
+test('build position ledger from native', async (t) => {
+  const file = fs.readFileSync('./__test__/data/doc4.xml', 'utf8')
+  const first = parseOwnershipForm(file)
+  const consistentFile = file.replace(
+    /<sharesOwnedFollowingTransaction>\s*<value>999.0</,
+    '<sharesOwnedFollowingTransaction><value>2999<'
+  )
+  const consistent = parseOwnershipForm(consistentFile)
+  const ledger = buildPositionLedger([first, consistent, first], '0000343434')
+
+  t.is(ledger.ownerCik, '0000343434')
+  t.is(ledger.entries.length, 18)
+  const commonStock = ledger.entries.filter((entry) => entry.securityTitle === 'Common Stock')
+  t.deepEqual(
+    commonStock.map((entry) => [entry.expectedShares, entry.shares, entry.discrepancy]),
+    [
+      [undefined, 999, undefined],
+      [2999, 2999, undefined],
+      [4999, 999, -4000],
+    ]
+  )
+  t.is(ledger.positions.length, 4)
+  t.is(ledger.positions[0].shares, 999)
+  t.is(ledger.positions[3].derivative, true)
+  t.is(ledger.positions[3].conversionOrExercisePrice, 50.55)
+  t.is(ledger.positions[3].expirationDate, '1980-12-25')
+
+  const holding = file.match(/<derivativeHolding>[\s\S]*?<\/derivativeHolding>/)[0]
+  const grants = parseOwnershipForm(
+    file.replace(
+      holding,
+      holding.replace('<value>0</value>', '<value>1000</value>') +
+        holding
+          .replace('<value>50.55</value>', '<value>80</value>')
+          .replace('<value>0</value>', '<value>5000</value>')
+    )
+  )
+  const grantLedger = buildPositionLedger([grants], '0000343434')
+  t.is(grantLedger.discrepancyCount, 0)
+  t.deepEqual(
+    grantLedger.positions
+      .filter((position) => position.derivative)
+      .map((position) => [position.conversionOrExercisePrice, position.shares]),
+    [
+      [50.55, 1000],
+      [80, 5000],
+    ]
+  )
+
+  const unknownChange = parseOwnershipForm(
+    consistentFile.replace(
+      /<transactionAcquiredDisposedCode>\s*<value>A<\/value>\s*<\/transactionAcquiredDisposedCode>/,
+      ''
+    )
+  )
+  const unknownEntry = buildPositionLedger([first, unknownChange], '0000343434').entries[6]
+  t.is(unknownEntry.securityTitle, 'Common Stock')
+  t.is(unknownEntry.change, undefined)
+  t.is(unknownEntry.expectedShares, undefined)
+  t.is(unknownEntry.shares, 2999)
+  t.is(unknownEntry.discrepancy, undefined)
+
+  const otherIssuer = parseOwnershipForm(file.replace(/<issuerCik>\d+</, '<issuerCik>0000000001<'))
+  t.is(
+    t.throws(() => buildPositionLedger([first, otherIssuer], '0000343434')).message,
+    'form issuer 0000000001 does not match ledger issuer 1212121212'
+  )
+  t.is(
+    t.throws(() => buildPositionLedger([first])).message,
+    'forms share reporting owners 0000343434, 1212121212; pass the owner to replay'
+  )
+  t.is(
+    t.throws(() => buildPositionLedger([first], '0000000002')).message,
+    'form 4 for 2003-09-15 does not list ledger owner 0000000002'
+  )
+
+  const amended = parseOwnershipForm(
+    fs
+      .readFileSync('./__test__/data/doc4a.xml', 'utf8')
+      .replace(/<transactionShares>\s*<value>33333</, '<transactionShares><value>30000<')
+  )
+  const amendedLedger = buildPositionLedger([first, amended], '0000343434')
+  t.is(amendedLedger.entries.length, 6)
+  t.true(amendedLedger.entries.every((entry) => entry.formIndex === 1))
+  t.is(amendedLedger.entries[1].change, -30000)
+  t.is(
+    t.throws(() => buildPositionLedger([amended], '0000343434')).message,
+    'amendment 4/A for 2003-09-15 has no earlier form to replace'
+  )
+})
+
 test('parse form 3 from native', async (t) => {
   const startTime = Date.now()
   const file = fs.readFileSync('./__test__/data/doc3.xml', 'utf8')
@@ -116,7 +599,7 @@ test('parse form 3 from native', async (t) => {
     },
     ownershipNature: {
       directOrIndirectOwnership: { value: 'D', footnoteIds: [] },
-      natureOfOwnership: { value: '', footnoteIds: [] },
+      natureOfOwnership: { footnoteIds: [] },
     },
   })
 
@@ -185,7 +668,7 @@ test('parse form 3/A from native', async (t) => {
     },
     ownershipNature: {
       directOrIndirectOwnership: { value: 'D', footnoteIds: [] },
-      natureOfOwnership: { value: '', footnoteIds: [] },
+      natureOfOwnership: { footnoteIds: [] },
     },
   })
 
@@ -259,7 +742,7 @@ test('parse form 4 from native', async (t) => {
       equitySwapInvolved: true,
       footnoteIds: ['F1', 'F2', 'F3'],
     },
-    transactionTimeliness: { value: '', footnoteIds: ['F3'] },
+    transactionTimeliness: { footnoteIds: ['F3'] },
     transactionAmounts: {
       shares: { value: 2000, footnoteIds: [] },
       pricePerShare: { value: 0, footnoteIds: [] },
@@ -373,7 +856,7 @@ test('parse form 4/A from native', async (t) => {
       equitySwapInvolved: true,
       footnoteIds: ['F1', 'F2', 'F3'],
     },
-    transactionTimeliness: { value: '', footnoteIds: ['F3'] },
+    transactionTimeliness: { footnoteIds: ['F3'] },
     transactionAmounts: {
       shares: { value: 2000, footnoteIds: [] },
       pricePerShare: { value: 0, footnoteIds: [] },
@@ -507,7 +990,7 @@ test('parse form 5 from native', async (t) => {
     },
     ownershipNature: {
       directOrIndirectOwnership: { value: 'D', footnoteIds: [] },
-      natureOfOwnership: { value: '', footnoteIds: [] },
+      natureOfOwnership: { footnoteIds: [] },
     },
   })
 
@@ -619,7 +1102,7 @@ test('parse form 5/A from native', async (t) => {
     },
     ownershipNature: {
       directOrIndirectOwnership: { value: 'D', footnoteIds: [] },
-      natureOfOwnership: { value: '', footnoteIds: [] },
+      natureOfOwnership: { footnoteIds: [] },
     },
   })
 
@@ -688,7 +1171,7 @@ test('parse form 13f-ctr from native', async (t) => {
         returnCopyFlag: false,
       },
       liveTestFlag: 'LIVE',
-      periodOfReport: '12-31-2021',
+      periodOfReport: '2021-12-31',
     },
   })
 
@@ -696,7 +1179,7 @@ test('parse form 13f-ctr from native', async (t) => {
   t.deepEqual(formData, {
     coverPage: {
       reportType: '13F HOLDINGS REPORT',
-      reportCalendarOrQuarter: '12-31-2021',
+      reportCalendarOrQuarter: '2021-12-31',
       crdNumber: 777777777,
       secFileNumber: '333-78445',
       provideInfoForInstruction5: false,
@@ -718,7 +1201,7 @@ test('parse form 13f-ctr from native', async (t) => {
       signature: 'dsfsd',
       city: 'adsd',
       stateOrCountry: 'AZ',
-      signatureDate: '11-03-2020',
+      signatureDate: '2020-11-03',
     },
     summaryPage: {
       otherIncludedManagersCount: 0,
@@ -755,7 +1238,7 @@ test('parse form 13f-ctra from native', async (t) => {
         returnCopyFlag: false,
       },
       liveTestFlag: 'LIVE',
-      periodOfReport: '12-31-2021',
+      periodOfReport: '2021-12-31',
       denovoRequest: true,
     },
   })
@@ -764,7 +1247,7 @@ test('parse form 13f-ctra from native', async (t) => {
   t.deepEqual(formData, {
     coverPage: {
       reportType: '13F HOLDINGS REPORT',
-      reportCalendarOrQuarter: '12-31-2021',
+      reportCalendarOrQuarter: '2021-12-31',
       crdNumber: 777777777,
       secFileNumber: '333-785445',
       isAmendment: true,
@@ -788,7 +1271,7 @@ test('parse form 13f-ctra from native', async (t) => {
       signature: 'sdfs',
       city: 'fsdf',
       stateOrCountry: 'AK',
-      signatureDate: '11-09-2021',
+      signatureDate: '2021-11-09',
     },
     summaryPage: {
       otherIncludedManagersCount: 0,
@@ -828,14 +1311,14 @@ test('parse form 13f-hr from native', async (t) => {
         emailAddress: 'ASDA@yahoo.com',
         phoneNumber: '222-222-2222',
       },
-      periodOfReport: '12-31-2021',
+      periodOfReport: '2021-12-31',
     },
   })
 
   const formData = result.formData
   t.deepEqual(formData, {
     coverPage: {
-      reportCalendarOrQuarter: '12-31-2021',
+      reportCalendarOrQuarter: '2021-12-31',
       filingManager: {
         name: 'BIG FUND TRUST inc',
         address: {
@@ -858,7 +1341,7 @@ test('parse form 13f-hr from native', async (t) => {
       signature: 'asdasd',
       city: 'asdd',
       stateOrCountry: 'AZ',
-      signatureDate: '11-02-2021',
+      signatureDate: '2021-11-02',
     },
     summaryPage: {
       otherIncludedManagersCount: 0,
@@ -899,14 +1382,14 @@ test('parse form 13f-hra from native', async (t) => {
         emailAddress: 'sds@yahoo.com',
         phoneNumber: '222-788-7777',
       },
-      periodOfReport: '12-31-2021',
+      periodOfReport: '2021-12-31',
     },
   })
 
   const formData = result.formData
   t.deepEqual(formData, {
     coverPage: {
-      reportCalendarOrQuarter: '12-31-2021',
+      reportCalendarOrQuarter: '2021-12-31',
       isAmendment: true,
       amendmentNumber: 45,
       amendmentInfo: {
@@ -934,7 +1417,7 @@ test('parse form 13f-hra from native', async (t) => {
       signature: 'dfsdf',
       city: 'sdfsdf',
       stateOrCountry: 'GA',
-      signatureDate: '11-01-2021',
+      signatureDate: '2021-11-01',
     },
     summaryPage: {
       otherIncludedManagersCount: 0,
@@ -975,14 +1458,14 @@ test('parse form 13f-nt from native', async (t) => {
         emailAddress: 'asdad@yahoo.com',
         phoneNumber: '222-222-2222',
       },
-      periodOfReport: '12-31-2021',
+      periodOfReport: '2021-12-31',
     },
   })
 
   const formData = result.formData
   t.deepEqual(formData, {
     coverPage: {
-      reportCalendarOrQuarter: '12-31-2021',
+      reportCalendarOrQuarter: '2021-12-31',
       filingManager: {
         name: 'BIG FUND TRUST inc',
         address: {
@@ -997,13 +1480,15 @@ test('parse form 13f-nt from native', async (t) => {
       crdNumber: 777777777,
       secFileNumber: '333-77777',
       otherManagersInfo: {
-        otherManager: {
-          name: 'BIG FUND TRUST inc',
-          cik: '0123456789',
-          form13FFileNumber: '028-7844',
-          crdNumber: 785474547,
-          secFileNumber: '333-78547',
-        },
+        otherManager: [
+          {
+            name: 'BIG FUND TRUST inc',
+            cik: '0123456789',
+            form13FFileNumber: '028-7844',
+            crdNumber: 785474547,
+            secFileNumber: '333-78547',
+          },
+        ],
       },
       provideInfoForInstruction5: false,
     },
@@ -1014,12 +1499,32 @@ test('parse form 13f-nt from native', async (t) => {
       signature: 'fdd',
       city: 'fd',
       stateOrCountry: 'AK',
-      signatureDate: '11-01-2021',
+      signatureDate: '2021-11-01',
     },
     documents: [],
   })
 })
 
+test('parse form 13f-nt with notifications and other managers from native', async (t) => {
+  const file = fs.readFileSync('./__test__/data/doc13f-nt-other-managers.xml', 'utf8')
+  const result = parseForm13F(file, { mode: 'Strict' })
+
+  t.deepEqual(result.headerData.filerInfo.notifications, {
+    emailAddress: ['filings@bigfund.com', 'compliance@bigfund.com'],
+  })
+  t.deepEqual(
+    result.formData.coverPage.otherManagersInfo.otherManager.map((manager) => manager.name),
+    ['BIG FUND TRUST inc', 'SMALL FUND ADVISORS LLC']
+  )
+  t.deepEqual(result.warnings, [])
+
+  const legacy = file.replace(/notificationEmailAddress>/g, 'emailAddress>')
+  t.is(
+    t.throws(() => parseForm13F(legacy, { mode: 'Strict' })).path,
+    'edgarSubmission/headerData/filerInfo/notifications/emailAddress[1]'
+  )
+})
+
 test('parse form 13f-nt/a from native', async (t) => {
   const startTime = Date.now()
   const file = fs.readFileSync('./__test__/data/doc13f-nta.xml', 'utf8')
@@ -1048,14 +1553,14 @@ test('parse form 13f-nt/a from native', async (t) => {
         emailAddress: 'sdfsdf@yahoo.com',
         phoneNumber: '222-222-2222',
       },
-      periodOfReport: '12-31-2021',
+      periodOfReport: '2021-12-31',
     },
   })
 
   const formData = result.formData
   t.deepEqual(formData, {
     coverPage: {
-      reportCalendarOrQuarter: '12-31-2021',
+      reportCalendarOrQuarter: '2021-12-31',
       isAmendment: true,
       amendmentNumber: 12,
       filingManager: {
@@ -1072,13 +1577,15 @@ test('parse form 13f-nt/a from native', async (t) => {
       crdNumber: 777777777,
       secFileNumber: '333-78545',
       otherManagersInfo: {
-        otherManager: {
-          cik: '0123456789',
-          crdNumber: 777777777,
-          form13FFileNumber: '028-54444',
-          name: 'BIG FUND TRUST inc',
-          secFileNumber: '333-78544',
-        },
+        otherManager: [
+          {
+            cik: '0123456789',
+            crdNumber: 777777777,
+            form13FFileNumber: '028-54444',
+            name: 'BIG FUND TRUST inc',
+            secFileNumber: '333-78544',
+          },
+        ],
       },
       provideInfoForInstruction5: false,
     },
@@ -1089,7 +1596,7 @@ test('parse form 13f-nt/a from native', async (t) => {
       signature: 'sdff',
       city: 'sdfsdf',
       stateOrCountry: 'AR',
-      signatureDate: '11-09-2021',
+      signatureDate: '2021-11-09',
     },
     documents: [],
   })
@@ -1123,4 +1630,599 @@ test('parse form 13f-table from native', async (t) => {
     },
     otherManager: [],
   })
+
+  for (const [parent, extra, path] of [
+    ['</ns1:votingAuthority>', '<ns1:Other>0</ns1:Other>', 'votingAuthority/Other'],
+    [
+      '</ns1:shrsOrPrnAmt>',
+      '<ns1:sshPrnamtClass>A</ns1:sshPrnamtClass>',
+      'shrsOrPrnAmt/sshPrnamtClass',
+    ],
+    ['</ns1:infoTable>', '<ns1:sector>TECH</ns1:sector>', 'sector'],
+  ]) {
+    const unknown = file.replace(parent, extra + parent)
+    t.is(t.throws(() => parseForm13FTable(unknown, { mode: 'Strict' })).code, 'UNKNOWN_ELEMENT')
+    const lenient = parseForm13FTable(unknown)
+    t.is(lenient.entries.length, 169)
+    t.is(lenient.warnings[0].code, 'UNKNOWN_ELEMENT')
+    t.is(lenient.warnings[0].path, `informationTable/infoTable[1]/${path}`)
+  }
+})
+
+test('form 13f conformance fixtures from native', async (t) => {
+  const fixtures = [
+    ['doc13f-hr.xml', '13F-HR'],
+    ['doc13f-hra.xml', '13F-HR/A'],
+    ['doc13f-hra-new-holdings.xml', '13F-HR/A'],
+    ['doc13f-nt.xml', '13F-NT'],
+    ['doc13f-nt-other-managers.xml', '13F-NT'],
+    ['doc13f-nta.xml', '13F-NT/A'],
+    ['doc13f-nta-restatement.xml', '13F-NT/A'],
+    ['doc13f-ctr.xml', '13F-CTR'],
+    ['doc13f-ctra.xml', '13F-CTR/A'],
+    ['doc13f-ctra-restatement.xml', '13F-CTR/A'],
+  ]
+  const isoDate = /^\d{4}-\d{2}-\d{2}$/
+  for (const [fixture, submissionType] of fixtures) {
+    const result = parseForm13F(fs.readFileSync(`./__test__/data/${fixture}`, 'utf8'), {
+      mode: 'Strict',
+    })
+    const { coverPage, signatureBlock, summaryPage } = result.formData
+    t.deepEqual(result.warnings, [], fixture)
+    t.is(result.headerData.submissionType, submissionType, fixture)
+    t.regex(result.headerData.filerInfo.periodOfReport, isoDate, fixture)
+    t.regex(coverPage.reportCalendarOrQuarter, isoDate, fixture)
+    t.regex(signatureBlock.signatureDate, isoDate, fixture)
+    t.is(coverPage.isAmendment === true, submissionType.endsWith('/A'), fixture)
+    t.is(summaryPage === undefined, submissionType.startsWith('13F-NT'), fixture)
+  }
+  for (const fixture of ['doc13f-nta-restatement.xml', 'doc13f-ctra-restatement.xml']) {
+    const result = parseForm13F(fs.readFileSync(`./__test__/data/${fixture}`, 'utf8'))
+    t.deepEqual(result.formData.coverPage.amendmentInfo, { amendmentType: 'RESTATEMENT' }, fixture)
+  }
+
+  const file = fs.readFileSync('./__test__/data/doc13f-hra-new-holdings.xml', 'utf8')
+  const result = parseForm13F(file)
+  t.is(result.schemaVersion, 'X0202')
+  t.is(result.headerData.filerInfo.liveTestFlag, 'TEST')
+  t.deepEqual(result.headerData.filerInfo.notifications, {
+    emailAddress: ['filings@bigfund.com'],
+  })
+  t.deepEqual(result.formData.coverPage.amendmentInfo, {
+    amendmentType: 'NEW HOLDINGS',
+    confDeniedExpired: true,
+    dateDeniedExpired: '2022-06-30',
+    dateReported: '2022-02-14',
+    reasonForNonConfidentiality: 'Confidential treatment expired',
+  })
+  t.is(result.formData.coverPage.reportType, '13F COMBINATION REPORT')
+  t.is(result.formData.coverPage.form13FFileNumber, '028-12345')
+  t.true(result.formData.coverPage.provideInfoForInstruction5)
+  t.deepEqual(result.formData.coverPage.otherManagersInfo.otherManager, [
+    {
+      cik: '0000987654',
+      form13FFileNumber: '028-54321',
+      crdNumber: 123456,
+      secFileNumber: '801-11111',
+      name: 'SMALL FUND ADVISORS LLC',
+    },
+  ])
+  t.deepEqual(result.formData.summaryPage.otherManagers[0], {
+    sequenceNumber: 1,
+    manager: {
+      cik: '0000555555',
+      form13FFileNumber: '028-55555',
+      crdNumber: 555555,
+      secFileNumber: '801-55555',
+      name: 'BIG FUND ADVISORS LLC',
+    },
+  })
+
+  const documentsFile = file.replace(
+    '</formData>',
+    '<documents><document><conformedName>cover.pdf</conformedName>' +
+      '<conformedDocumentType>EX-99</conformedDocumentType><pages>2</pages>' +
+      '</document></documents></formData>'
+  )
+  t.is(t.throws(() => parseForm13F(documentsFile, { mode: 'Strict' })).code, 'UNKNOWN_ELEMENT')
+  const documents = parseForm13F(documentsFile)
+  t.deepEqual(documents.formData.documents, [
+    { conformedName: 'cover.pdf', conformedDocumentType: 'EX-99' },
+  ])
+  t.is(documents.warnings[0].path, 'edgarSubmission/formData/documents/document/pages')
+
+  const misnamedFile = file.replace(/dateReported>/g, 'dataReported>')
+  t.is(t.throws(() => parseForm13F(misnamedFile, { mode: 'Strict' })).code, 'UNKNOWN_ELEMENT')
+  const misnamed = parseForm13F(misnamedFile)
+  t.is(misnamed.formData.coverPage.amendmentInfo.dateReported, undefined)
+  t.is(misnamed.warnings[0].code, 'UNKNOWN_ELEMENT')
+  t.is(misnamed.warnings[0].path, 'edgarSubmission/formData/coverPage/amendmentInfo/dataReported')
+  const invalid = t.throws(() =>
+    parseForm13F(file.replace('<dateReported>02-14-2022<', '<dateReported>2022-02-14<'), {
+      mode: 'Strict',
+    })
+  )
+  t.is(invalid.code, 'INVALID_VALUE')
+})
+
+test('parse form 13f codes from native', async (t) => {
+  const form = fs
+    .readFileSync('./__test__/data/doc13f-hr.xml', 'utf8')
+    .replace('<reportType>13F HOLDINGS REPORT<', '<reportType>HOLDINGS<')
+  t.is(
+    t.throws(() => parseForm13F(form, { mode: 'Strict' })).path,
+    'edgarSubmission/formData/coverPage/reportType'
+  )
+  const result = parseForm13F(form, { mode: 'Lenient' })
+  t.is(result.headerData.submissionType, '13F-HR')
+  t.is(result.formData.coverPage.reportType, undefined)
+  t.deepEqual(result.warnings.map((warning) => warning.code), ['INVALID_VALUE'])
+  t.false(isForm13FAmendment(result.headerData.submissionType))
+  t.true(isForm13FAmendment('13F-NT/A'))
+  t.false(isForm13FAmendment('13F-XX'))
+
+  const largeTable = fs.readFileSync('./__test__/data/doc13f-table-large.xml', 'utf8')
+  const table = largeTable.replace(
+    '<ns1:investmentDiscretion>DFND<',
+    '<ns1:investmentDiscretion>SHARED<'
+  )
+  const lenientTable = parseForm13FTable(table, { mode: 'Lenient' })
+  t.deepEqual(
+    lenientTable.entries.map((entry) => entry.investmentDiscretion),
+    ['SOLE', undefined, 'SOLE']
+  )
+  t.is(lenientTable.entries[1].value, parseForm13FTable(largeTable).entries[1].value)
+  t.is(lenientTable.warnings[0].path, 'informationTable/infoTable[2]/investmentDiscretion')
+  t.is(t.throws(() => parseForm13FTable(table, { mode: 'Strict' })).code, 'INVALID_VALUE')
+
+  const amountType = parseForm13FTable(
+    largeTable.replace('<ns1:sshPrnamtType>PRN<', '<ns1:sshPrnamtType>BOND<')
+  )
+  t.is(amountType.entries.length, 3)
+  t.is(amountType.entries[2].sharesOrPrintAmount.sharesOrPrintType, undefined)
+  t.is(amountType.warnings[0].path, 'informationTable/infoTable[3]/shrsOrPrnAmt/sshPrnamtType')
+})
+
+test('parse form 13f table with huge positions from native', async (t) => {
+  const file = fs.readFileSync('./__test__/data/doc13f-table-large.xml', 'utf8')
+  const result = parseForm13FTable(file)
+
+  t.is(result.entries.length, 3)
+  t.deepEqual(result.warnings, [])
+  t.deepEqual(result.entries[1].votingAuthority, {
+    sole: 3567890123,
+    shared: 2200000000,
+    none: 0,
+  })
+  t.is(result.entries[1].value, 1654321987654)
+  t.is(result.entries[1].sharesOrPrintAmount.amount, 3912345678)
+  t.is(result.entries[2].sharesOrPrintAmount.amount, 10000000000)
+
+  const padded = parseForm13FTable(
+    file.replace('<ns1:Sole>3567890123<', '<ns1:Sole>\n  3567890123\n<'),
+    { mode: 'Strict' }
+  )
+  t.is(padded.entries[1].votingAuthority.sole, 3567890123)
+})
+
+test('normalize form 13f values from native', async (t) => {
+  const form = parseForm13F(fs.readFileSync('./__test__/data/doc13f-hr.xml', 'utf8'))
+  const tableFile = fs.readFileSync('./__test__/data/doc13f-table.xml', 'utf8')
+  const table = parseForm13FTable(tableFile)
+  table.entries[3].value = 700000
+  table.entries[3].sharesOrPrintAmount.amount = 1000
+  table.entries[4].value = 1876000
+  table.entries[5].value = 9300000000000000
+  const result = normalizeForm13FValues(table, form)
+
+  t.true(result.reportedInThousands)
+  t.is(result.entries[1].value, 7423000)
+  t.is(result.entries[5].value, 9300000000000000)
+  t.deepEqual(
+    result.valueFlags.map((flag) => [flag.index, flag.kind]),
+    [
+      [4, 'ThousandTimesTooHigh'],
+      [5, 'Overflow'],
+    ]
+  )
+
+  form.headerData.filerInfo.periodOfReport = '2023-03-31'
+  const dollars = normalizeForm13FValues(parseForm13FTable(tableFile), form)
+  t.false(dollars.reportedInThousands)
+  t.is(dollars.entries[1].value, 7423)
+  t.is(dollars.valueFlags.find((flag) => flag.index === 1), undefined)
+  t.deepEqual(dollars.valueFlags.find((flag) => flag.index === 14), {
+    index: 14,
+    impliedPrice: 22 / 10057,
+    kind: 'ThousandTimesTooLow',
+  })
+})
+
+test('diff form 13f tables from native', async (t) => {
+  const file = fs.readFileSync('./__test__/data/doc13f-table-large.xml', 'utf8')
+  const previous = parseForm13FTable(file)
+  const current = parseForm13FTable(
+    file
+      .replace('<ns1:sshPrnamt>1302457916<', '<ns1:sshPrnamt>651228958<')
+      .replace('<ns1:value>312456789012<', '<ns1:value>170000000000<')
+      .replace('<ns1:cusip>91282CJZ5<', '<ns1:cusip>91282CKA8<')
+  )
+  const changes = diffForm13FTables(previous, current)
+
+  t.deepEqual(
+    changes.map((change) => [change.kind, change.cusip]),
+    [
+      ['Decreased', '037833100'],
+      ['Unchanged', '594918104'],
+      ['New', '91282CKA8'],
+      ['Closed', '91282CJZ5'],
+    ]
+  )
+  t.deepEqual(changes[0], {
+    kind: 'Decreased',
+    cusip: '037833100',
+    titleOfClass: 'COM',
+    nameOfIssuer: 'APPLE INC',
+    previousShares: 1302457916,
+    currentShares: 651228958,
+    shareChange: -651228958,
+    shareChangePercent: -50,
+    previousValue: 312456789012,
+    currentValue: 170000000000,
+    valueChange: -142456789012,
+    valueChangePercent: -45.59247679093601,
+    overflow: false,
+  })
+  t.is(changes[2].shareChangePercent, undefined)
+  t.is(changes[3].currentShares, 0)
+
+  const huge = (value) => ({
+    ...previous,
+    entries: [0, 1].map(() => ({ ...previous.entries[0], value })),
+  })
+  const [summed] = diffForm13FTables(huge(1), huge(9e18))
+  t.true(summed.overflow)
+  t.is(summed.currentValue, 2 ** 63)
+  const [changed] = diffForm13FTables(
+    { ...previous, entries: [{ ...previous.entries[0], value: -9e18 }] },
+    { ...previous, entries: [{ ...previous.entries[0], value: 9e18 }] }
+  )
+  t.true(changed.overflow)
+  t.is(changed.valueChange, 2 ** 63)
+  t.is(changed.valueChangePercent, -200)
+})
+
+test('aggregate form 13f table from native', async (t) => {
+  const file = fs.readFileSync('./__test__/data/doc13f-table-large.xml', 'utf8')
+  const extraRows = `
+    <ns1:infoTable>
+      <ns1:nameOfIssuer>APPLE INC</ns1:nameOfIssuer>
+      <ns1:titleOfClass>COM</ns1:titleOfClass>
+      <ns1:cusip>037833100</ns1:cusip>
+      <ns1:value>10000000000</ns1:value>
+      <ns1:shrsOrPrnAmt><ns1:sshPrnamt>50000000</ns1:sshPrnamt><ns1:sshPrnamtType>SH</ns1:sshPrnamtType></ns1:shrsOrPrnAmt>
+      <ns1:investmentDiscretion>DFND</ns1:investmentDiscretion>
+      <ns1:otherManager>1</ns1:otherManager>
+      <ns1:votingAuthority><ns1:Sole>0</ns1:Sole><ns1:Shared>50000000</ns1:Shared><ns1:None>0</ns1:None></ns1:votingAuthority>
+    </ns1:infoTable>
+    <ns1:infoTable>
+      <ns1:nameOfIssuer>APPLE INC</ns1:nameOfIssuer>
+      <ns1:titleOfClass>COM</ns1:titleOfClass>
+      <ns1:cusip>037833100</ns1:cusip>
+      <ns1:value>543210988</ns1:value>
+      <ns1:shrsOrPrnAmt><ns1:sshPrnamt>2500000</ns1:sshPrnamt><ns1:sshPrnamtType>SH</ns1:sshPrnamtType></ns1:shrsOrPrnAmt>
+      <ns1:putCall>Put</ns1:putCall>
+      <ns1:investmentDiscretion>SOLE</ns1:investmentDiscretion>
+      <ns1:votingAuthority><ns1:Sole>0</ns1:Sole><ns1:Shared>0</ns1:Shared><ns1:None>0</ns1:None></ns1:votingAuthority>
+    </ns1:infoTable>
+  </ns1:informationTable>`
+  const table = parseForm13FTable(file.replace('</ns1:informationTable>', extraRows))
+
+  const holdings = aggregateForm13FTable(table)
+  t.deepEqual(
+    holdings.map((holding) => [holding.cusip, holding.putCall, holding.entryCount]),
+    [
+      ['037833100', undefined, 2],
+      ['594918104', undefined, 1],
+      ['91282CJZ5', undefined, 1],
+      ['037833100', 'Put', 1],
+    ]
+  )
+  t.deepEqual(holdings[0], {
+    cusip: '037833100',
+    nameOfIssuer: 'APPLE INC',
+    titleOfClass: 'COM',
+    value: 322456789012,
+    sharesOrPrintAmount: { amount: 1352457916, sharesOrPrintType: 'SH' },
+    votingAuthority: { sole: 1245000000, shared: 50000000, none: 57457916 },
+    entryCount: 2,
+    overflow: false,
+    weight: 322456789012 / 1987198530864,
+  })
+
+  const combined = aggregateForm13FTable(table, { byPutCall: false })
+  t.is(combined.length, 3)
+  t.is(combined[0].value, 323000000000)
+  t.is(combined[0].entryCount, 3)
+
+  const principal = parseForm13FTable(
+    file.replace('</ns1:informationTable>', extraRows.replace('>SH<', '>PRN<'))
+  )
+  t.deepEqual(
+    aggregateForm13FTable(principal).map((holding) => [
+      holding.cusip,
+      holding.sharesOrPrintAmount.sharesOrPrintType,
+      holding.entryCount,
+    ]),
+    [
+      ['037833100', 'SH', 1],
+      ['594918104', 'SH', 1],
+      ['91282CJZ5', 'PRN', 1],
+      ['037833100', 'PRN', 1],
+      ['037833100', 'SH', 1],
+    ]
+  )
+
+  const huge = { ...table, entries: [0, 1].map(() => ({ ...table.entries[0], value: 9e18 })) }
+  const [saturated] = aggregateForm13FTable(huge)
+  t.true(saturated.overflow)
+  t.is(saturated.value, 2 ** 63)
+  t.is(saturated.entryCount, 2)
+  t.is(saturated.weight, 1)
+})
+
+test('apply form 13f amendment from native', async (t) => {
+  const original = parseForm13F(fs.readFileSync('./__test__/data/doc13f-hr.xml', 'utf8'))
+  const amended = parseForm13F(fs.readFileSync('./__test__/data/doc13f-hra.xml', 'utf8'))
+  const originalTable = parseForm13FTable(
+    fs.readFileSync('./__test__/data/doc13f-table.xml', 'utf8')
+  )
+  const amendedTable = parseForm13FTable(
+    fs.readFileSync('./__test__/data/doc13f-table-large.xml', 'utf8')
+  )
+
+  const restated = applyForm13FAmendment(original, originalTable, amended, amendedTable)
+  t.is(restated.amendmentType, 'RESTATEMENT')
+  t.is(restated.amendmentNumber, 45)
+  t.is(restated.originalEntryCount, 0)
+  t.deepEqual(
+    restated.entries.map((entry) => entry.cusip),
+    ['037833100', '594918104', '91282CJZ5']
+  )
+
+  const newHoldings = parseForm13F(
+    fs.readFileSync('./__test__/data/doc13f-hra-new-holdings.xml', 'utf8')
+  )
+  const added = applyForm13FAmendment(original, originalTable, newHoldings, amendedTable)
+  t.is(added.amendmentType, 'NEW HOLDINGS')
+  t.is(added.amendmentNumber, 46)
+  t.is(added.originalEntryCount, originalTable.entries.length)
+  t.is(added.entries.length, originalTable.entries.length + 3)
+  t.deepEqual(added.entries[0], originalTable.entries[0])
+  t.is(added.entries[originalTable.entries.length].cusip, '037833100')
+
+  t.is(
+    t.throws(() => applyForm13FAmendment(original, originalTable, original, amendedTable)).message,
+    'submission 13F-HR is not an amendment'
+  )
+  amended.formData.coverPage.reportCalendarOrQuarter = '2022-03-31'
+  t.is(
+    t.throws(() => applyForm13FAmendment(original, originalTable, amended, amendedTable)).message,
+    'amendment period 2022-03-31 does not match original period 2021-12-31'
+  )
+})
+
+test('validate form 13f table from native', async (t) => {
+  const form = parseForm13F(fs.readFileSync('./__test__/data/doc13f-hr.xml', 'utf8'))
+  const table = parseForm13FTable(fs.readFileSync('./__test__/data/doc13f-table-large.xml', 'utf8'))
+
+  t.deepEqual(
+    validateForm13FTable(form, table).map((discrepancy) => [
+      discrepancy.kind,
+      discrepancy.entryIndex,
+    ]),
+    [
+      ['EntryTotalMismatch', undefined],
+      ['ValueTotalMismatch', undefined],
+      ['UnknownOtherManager', 1],
+    ]
+  )
+
+  form.formData.summaryPage = {
+    ...form.formData.summaryPage,
+    otherIncludedManagersCount: 1,
+    tableEntryTotal: 3,
+    tableValueTotal: 1976655319876,
+    otherManagers: [{ sequenceNumber: 1, manager: { name: 'BIG FUND ADVISORS LLC' } }],
+  }
+  t.deepEqual(validateForm13FTable(form, table), [])
+
+  const huge = { ...table, entries: table.entries.map((entry) => ({ ...entry, value: 9e18 })) }
+  const [overflow] = validateForm13FTable(form, huge)
+  t.is(overflow.kind, 'ValueTotalMismatch')
+  t.true(overflow.message.endsWith('information table values overflow a 64-bit sum'))
+
+  const notice = parseForm13F(fs.readFileSync('./__test__/data/doc13f-nt.xml', 'utf8'))
+  t.is(validateForm13FTable(notice, table)[0].kind, 'MissingSummaryPage')
+})
+
+test('validate and enrich security identifiers from native', async (t) => {
+  t.true(validateCusip('037833100'))
+  t.false(validateCusip('037833101'))
+  t.false(validateCusip('03783310'))
+  t.true(validateFigi('BBG000B9XRY4'))
+  t.false(validateFigi('BBG000B9XRY5'))
+  t.false(validateFigi('02079K107743'))
+  t.is(issuerCusip('037833100'), '037833')
+  t.is(issuerCusip('037833101'), null)
+  t.is(cusipToIsin('037833100'), 'US0378331005')
+  t.is(cusipToIsin('G0177J108'), null)
+  t.true(validateCusip('0378331*1'))
+  t.is(cusipToIsin('0378331*1'), null)
+
+  const table = parseForm13FTable(fs.readFileSync('./__test__/data/doc13f-table.xml', 'utf8'))
+  const tickers = parseTickerCsv('cusip,ticker\n"00724F101",ADBE\n02079k107,GOOG\n')
+  t.deepEqual(tickers, { '00724F101': 'ADBE', '02079K107': 'GOOG' })
+  t.deepEqual(parseTickerCsv('"037833100","AAPL","Apple, Inc."\n"00724F101","ADBE ""X""",\n'), {
+    '037833100': 'AAPL',
+    '00724F101': 'ADBE "X"',
+  })
+  const identifiers = enrichForm13FTable(table, tickers)
+  t.is(identifiers.length, table.entries.length)
+  t.deepEqual(identifiers[1], {
+    cusip: '00724F101',
+    cusipValid: true,
+    figiValid: false,
+    issuerCusip: '00724F',
+    isin: 'US00724F1012',
+    ticker: 'ADBE',
+  })
+  t.is(enrichForm13FTable(table)[1].ticker, undefined)
+})
+
+test('parse form 144 from native', async (t) => {
+  const file = fs.readFileSync('./__test__/data/doc144.xml', 'utf8')
+  const result = parseForm144(file)
+
+  t.is(result.submissionType, '144')
+  t.is(result.filerCik, '0001214128')
+  t.is(result.liveTestFlag, 'LIVE')
+  t.is(result.issuer.cik, '0000320193')
+  t.is(result.issuer.address.stateOrCountry, 'CA')
+  t.deepEqual(result.seller, { name: 'Jane Q. Insider', relationshipsToIssuer: ['Officer'] })
+
+  t.is(result.securitiesToBeSold.length, 1)
+  t.deepEqual(result.securitiesToBeSold[0], {
+    classTitle: 'Common',
+    broker: {
+      name: 'Morgan Stanley Smith Barney LLC Executive Financial Services',
+      address: {
+        street1: '1 New York Plaza',
+        street2: '8th Floor',
+        city: 'New York',
+        stateOrCountry: 'NY',
+        zipCode: '10004',
+      },
+    },
+    unitsToBeSold: 38500,
+    aggregateMarketValue: 6593895,
+    unitsOutstanding: 15634232000,
+    approximateSaleDate: '2023-10-02',
+    exchange: 'NASDAQ',
+  })
+
+  t.deepEqual(result.securitiesAcquired[0], {
+    classTitle: 'Common',
+    acquiredDate: '2023-10-01',
+    natureOfAcquisition: 'Restricted Stock Vesting',
+    acquiredFrom: 'Issuer',
+    isGift: false,
+    amountAcquired: 38500,
+    paymentDate: '2023-10-01',
+    natureOfPayment: 'Services Rendered',
+  })
+
+  t.is(result.nothingToReportPastThreeMonths, false)
+  t.is(result.salesPastThreeMonths.length, 1)
+  t.is(result.salesPastThreeMonths[0].saleDate, '2023-08-15')
+  t.is(result.salesPastThreeMonths[0].grossProceeds, 2147280)
+  t.is(result.noticeDate, '2023-10-02')
+  t.deepEqual(result.planAdoptionDates, ['2023-05-12'])
+  t.is(result.signature, '/s/ Jane Q. Insider')
+  t.deepEqual(result.warnings, [])
+
+  const badDate = file.replace('<saleDate>08/15/2023<', '<saleDate>2023-08-15<')
+  t.is(
+    t.throws(() => parseForm144(badDate, { mode: 'Strict' })).path,
+    'edgarSubmission/formData/securitiesSoldInPast3Months/saleDate'
+  )
+  const unknown = file
+    .replace('<submissionType>144<', '<submissionType>145<')
+    .replace('<issuerContactPhone>', '<issuerFax>(408) 996-1011</issuerFax><issuerContactPhone>')
+  t.is(t.throws(() => parseForm144(unknown, { mode: 'Strict' })).code, 'INVALID_VALUE')
+  const lenient = parseForm144(unknown)
+  t.is(lenient.submissionType, undefined)
+  t.deepEqual(
+    lenient.warnings.map((warning) => [warning.code, warning.path]),
+    [
+      ['INVALID_VALUE', 'edgarSubmission/headerData/submissionType'],
+      ['UNKNOWN_ELEMENT', 'edgarSubmission/formData/issuerInfo/issuerFax'],
+    ]
+  )
+})
+
+test('parse schedule 13d from native', async (t) => {
+  const file = fs.readFileSync('./__test__/data/doc13d.xml', 'utf8')
+  const result = parseSchedule13D(file)
+
+  t.is(result.submissionType, 'SCHEDULE 13D')
+  t.is(result.eventDate, '2024-12-18')
+  t.deepEqual(result.issuer, {
+    cik: '0001108134',
+    name: 'Example Therapeutics, Inc.',
+    cusip: '09073M104',
+  })
+  t.is(result.reportingPersons.length, 2)
+  t.deepEqual(result.reportingPersons[1], {
+    name: 'John Activist',
+    citizenshipOrOrganization: 'X1',
+    soleVotingPower: 15000,
+    sharedVotingPower: 4250000,
+    soleDispositivePower: 15000,
+    sharedDispositivePower: 4250000,
+    aggregateAmountOwned: 4265000,
+    percentOfClass: 7.32,
+    typeOfReportingPerson: ['IN', 'HC'],
+  })
+  t.deepEqual(result.items.map((item) => item.number), [1, 3, 4, 5])
+  t.deepEqual(result.items[3].fields[1], {
+    path: 'transactionDesc/transactionDescription',
+    value: 'See Schedule A.',
+  })
+  t.deepEqual(result.warnings, [])
+
+  const unknown = file.replace(
+    '<reportingPersonName>John Activist<',
+    '<reportingPersonNickname>JA</reportingPersonNickname><reportingPersonName>John Activist<'
+  )
+  const error = t.throws(() => parseSchedule13D(unknown, { mode: 'Strict' }))
+  t.is(error.code, 'UNKNOWN_ELEMENT')
+  t.is(
+    error.path,
+    'edgarSubmission/formData/reportingPersons/reportingPersonInfo[2]/reportingPersonNickname'
+  )
+  t.is(parseSchedule13D(unknown).warnings[0].code, 'UNKNOWN_ELEMENT')
+})
+
+test('parse schedule 13g from native', async (t) => {
+  const file = fs.readFileSync('./__test__/data/doc13g.xml', 'utf8')
+  const result = parseSchedule13G(file)
+
+  t.is(result.submissionType, 'SCHEDULE 13G/A')
+  t.is(result.amendmentNo, 3)
+  t.is(result.eventDate, '2024-12-31')
+  t.is(result.issuer.cusip, '037833100')
+  t.deepEqual(result.reportingPersons, [
+    {
+      name: 'Index Fund Advisors, Inc.',
+      citizenshipOrOrganization: 'DE',
+      soleVotingPower: 957000000,
+      sharedVotingPower: 0,
+      soleDispositivePower: 1040000000,
+      sharedDispositivePower: 0,
+      aggregateAmountOwned: 1040000000,
+      percentOfClass: 6.9,
+      typeOfReportingPerson: ['HC'],
+    },
+  ])
+  t.deepEqual(result.items[1], {
+    number: 4,
+    fields: [
+      { path: 'amountBeneficiallyOwned', value: '1040000000' },
+      { path: 'classPercent', value: '6.9' },
+      { path: 'numberOfSharesPersonHas/solePowerOrDirectToVote', value: '957000000' },
+      { path: 'numberOfSharesPersonHas/sharedPowerOrDirectToVote', value: '0' },
+    ],
+  })
+  t.deepEqual(result.warnings, [])
 })
@@ -7,17 +7,21 @@ export interface Form13F {
   schemaVersion?: string
   headerData: HeaderData
   formData: FormData
+  warnings: Array<Diagnostic>
 }
 export interface HeaderData {
-  submissionType: string
+  /** Unset when lenient parsing meets an invalid submission type. */
+  submissionType?: '13F-HR' | '13F-HR/A' | '13F-NT' | '13F-NT/A' | '13F-CTR' | '13F-CTR/A'
   filerInfo: FilerInfo
 }
 export interface FilerInfo {
-  liveTestFlag: string
+  /** Unset when lenient parsing meets an invalid flag. */
+  liveTestFlag?: 'LIVE' | 'TEST'
   flags?: Flags
   filer: Filer
   contact?: Contact
   notifications?: Notifications
+  /** A `YYYY-MM-DD` date. */
   periodOfReport: string
   denovoRequest?: boolean
 }
@@ -40,7 +44,7 @@ export interface Contact {
   emailAddress?: string
 }
 export interface Notifications {
-  emailAddress?: string
+  emailAddress: Array<string>
 }
 export interface FormData {
   coverPage: CoverPage
@@ -49,12 +53,14 @@ export interface FormData {
   documents?: Array<OtherDocument>
 }
 export interface CoverPage {
+  /** The `YYYY-MM-DD` end of the calendar year or quarter the report covers. */
   reportCalendarOrQuarter: string
   isAmendment?: boolean
   amendmentNumber?: number
   amendmentInfo?: AmendmentInfo
   filingManager: FilingManager
-  reportType: string
+  /** Unset when lenient parsing meets an invalid report type. */
+  reportType?: '13F HOLDINGS REPORT' | '13F NOTICE' | '13F COMBINATION REPORT'
   form13FFileNumber?: string
   crdNumber?: number
   secFileNumber?: string
@@ -63,9 +69,12 @@ export interface CoverPage {
   additionalInformation?: string
 }
 export interface AmendmentInfo {
-  amendmentType?: string
+  amendmentType?: 'RESTATEMENT' | 'NEW HOLDINGS'
+  /** Whether the amendment reports holdings whose confidential treatment was denied or expired. */
   confDeniedExpired?: boolean
-  dataDeniedExpired?: string
+  /** A `YYYY-MM-DD` date. */
+  dateDeniedExpired?: string
+  /** A `YYYY-MM-DD` date. */
   dateReported?: string
   reasonForNonConfidentiality?: string
 }
@@ -81,7 +90,8 @@ export interface Address {
   zipCode: string
 }
 export interface OtherManagersInfo {
-  otherManager?: OtherManager
+  /** Managers reporting on the filer's behalf, as listed on a 13F notice. */
+  otherManager: Array<OtherManager>
 }
 export interface OtherManager {
   cik?: string
@@ -97,6 +107,7 @@ export interface SignatureBlock {
   signature: string
   city: string
   stateOrCountry: string
+  /** A `YYYY-MM-DD` date. */
   signatureDate: string
 }
 export interface SummaryPage {
@@ -118,6 +129,7 @@ export interface OtherDocument {
 }
 export interface Form13FTable {
   entries: Array<TableEntry>
+  warnings: Array<Diagnostic>
 }
 export interface TableEntry {
   nameOfIssuer: string
@@ -126,22 +138,262 @@ export interface TableEntry {
   figi?: string
   value: number
   sharesOrPrintAmount: SharesOrPrintAmount
-  putCall?: string
-  investmentDiscretion: string
+  putCall?: 'Put' | 'Call'
+  /** Unset when lenient parsing meets an invalid discretion code. */
+  investmentDiscretion?: 'SOLE' | 'DFND' | 'OTR'
   otherManager?: Array<number>
   votingAuthority: VotingAuthority
 }
 export interface SharesOrPrintAmount {
   amount: number
-  sharesOrPrintType: string
+  /** Unset when lenient parsing meets an invalid amount type. */
+  sharesOrPrintType?: 'SH' | 'PRN'
 }
 export interface VotingAuthority {
   sole: number
   shared: number
   none: number
 }
-export function parseForm13F(form: string): Form13F
-export function parseForm13FTable(table: string): Form13FTable
+/** Whether a 13F submission type such as `13F-HR/A` is an amendment. */
+export function isForm13FAmendment(submissionType: string): boolean
+export function parseForm13F(form: string, options?: ParseOptions | undefined | null): Form13F
+export function parseForm13FTable(table: string, options?: ParseOptions | undefined | null): Form13FTable
+export interface AggregateOptions {
+  /**
+   * Keep puts, calls and the underlying security as separate holdings. Defaults to true, as an
+   * option row reports the value of its underlying shares rather than of the option.
+   */
+  byPutCall?: boolean
+}
+export interface AggregatedHolding {
+  cusip: string
+  nameOfIssuer: string
+  titleOfClass: string
+  /** Only set when aggregating by put/call. */
+  putCall?: 'Put' | 'Call'
+  value: number
+  sharesOrPrintAmount: SharesOrPrintAmount
+  votingAuthority: VotingAuthority
+  /** Number of information table rows combined into the holding. */
+  entryCount: number
+  /**
+   * Set when a sum exceeds the 64-bit integer range; the amounts then stop at the largest
+   * representable value.
+   */
+  overflow: boolean
+  /** Share of the table's total value, between 0 and 1. */
+  weight: number
+}
+/**
+ * Combines the information table rows of each security, which filers split by investment
+ * discretion and other manager, into one holding in order of first appearance. Share and
+ * principal amounts of the same security are kept as separate holdings.
+ */
+export function aggregateForm13FTable(table: Form13FTable, options?: AggregateOptions | undefined | null): Array<AggregatedHolding>
+export interface EffectiveHoldings {
+  amendmentType: 'RESTATEMENT' | 'NEW HOLDINGS'
+  amendmentNumber?: number
+  /** The holdings for the quarter after applying the amendment. */
+  entries: Array<TableEntry>
+  /** Number of leading entries taken from the original table. */
+  originalEntryCount: number
+}
+/**
+ * Combines a 13F-HR/A with the filing it amends. A restatement replaces the original
+ * information table, while a new holdings amendment only lists holdings the original omitted.
+ * Amendments are applied one at a time, passing the previous result as the original table.
+ */
+export function applyForm13FAmendment(original: Form13F, originalTable: Form13FTable, amended: Form13F, amendedTable: Form13FTable): EffectiveHoldings
+export interface HoldingChange {
+  kind: HoldingChangeKind
+  cusip: string
+  titleOfClass: string
+  putCall?: 'Put' | 'Call'
+  nameOfIssuer: string
+  previousShares: number
+  currentShares: number
+  shareChange: number
+  /** Percent change in shares, absent for new positions. */
+  shareChangePercent?: number
+  previousValue: number
+  currentValue: number
+  valueChange: number
+  /** Percent change in value, absent when the previous value is zero. */
+  valueChangePercent?: number
+  /**
+   * Set when a sum or change exceeds the 64-bit integer range; the amounts then stop at the
+   * nearest representable value.
+   */
+  overflow: boolean
+}
+export const enum HoldingChangeKind {
+  New = 'New',
+  Closed = 'Closed',
+  Increased = 'Increased',
+  Decreased = 'Decreased',
+  Unchanged = 'Unchanged'
+}
+/**
+ * Compares the holdings of two quarters, keyed by CUSIP, title of class and put/call. Entries
+ * sharing a key within a table, such as rows split by other manager, are summed first. Values
+ * are compared as reported, so tables on either side of the 2023 unit change should be
+ * normalized with `normalizeForm13FValues` first.
+ */
+export function diffForm13FTables(previous: Form13FTable, current: Form13FTable): Array<HoldingChange>
+export interface Form13FDiscrepancy {
+  kind: DiscrepancyKind
+  message: string
+  /** Index of the information table entry, for entry-level discrepancies. */
+  entryIndex?: number
+}
+export const enum DiscrepancyKind {
+  /** The form has no summary page to check the table against. */
+  MissingSummaryPage = 'MissingSummaryPage',
+  /** `tableEntryTotal` differs from the number of table entries. */
+  EntryTotalMismatch = 'EntryTotalMismatch',
+  /** `tableValueTotal` differs from the sum of the table values, or the sum overflows. */
+  ValueTotalMismatch = 'ValueTotalMismatch',
+  /** `otherIncludedManagersCount` differs from the number of listed other managers. */
+  OtherManagersCountMismatch = 'OtherManagersCountMismatch',
+  /** An entry references an other manager sequence number the summary page does not list. */
+  UnknownOtherManager = 'UnknownOtherManager'
+}
+/**
+ * Checks the totals and other manager list reported on the summary page of a Form 13F against
+ * its information table. Totals are compared in the units the filing reports them in.
+ */
+export function validateForm13FTable(form: Form13F, table: Form13FTable): Array<Form13FDiscrepancy>
+export interface NormalizedForm13FTable {
+  periodOfReport: string
+  /** Whether values were converted from thousands of dollars. */
+  reportedInThousands: boolean
+  /** Entries with `value` in whole dollars. */
+  entries: Array<TableEntry>
+  valueFlags: Array<ValueFlag>
+}
+/** An entry whose value is implausible for its share count or could not be converted. */
+export interface ValueFlag {
+  /** Index of the entry in the table. */
+  index: number
+  /** Absent for entries reported as a principal amount. */
+  impliedPrice?: number
+  kind: ValueFlagKind
+}
+export const enum ValueFlagKind {
+  /** The value looks 1000x too small, as if reported in thousands of dollars. */
+  ThousandTimesTooLow = 'ThousandTimesTooLow',
+  /** The value looks 1000x too large, as if reported in dollars before 2023. */
+  ThousandTimesTooHigh = 'ThousandTimesTooHigh',
+  /** The value in thousands does not fit in whole dollars and is left as reported. */
+  Overflow = 'Overflow'
+}
+/**
+ * Converts information table values to whole dollars using the period of report of the
+ * companion Form 13F, and flags share entries whose implied price per share is implausible but
+ * becomes typical when corrected by 1000x.
+ */
+export function normalizeForm13FValues(table: Form13FTable, form: Form13F): NormalizedForm13FTable
+export interface Form144 {
+  /** Unset when lenient parsing meets an invalid submission type. */
+  submissionType?: '144' | '144/A'
+  filerCik?: string
+  liveTestFlag?: 'LIVE' | 'TEST'
+  issuer: Form144Issuer
+  /** The person for whose account the securities are to be sold. */
+  seller: Form144Seller
+  securitiesToBeSold: Array<SecuritiesToBeSold>
+  securitiesAcquired: Array<SecuritiesAcquired>
+  nothingToReportPastThreeMonths?: boolean
+  salesPastThreeMonths: Array<PastSale>
+  remarks?: string
+  noticeDate?: string
+  planAdoptionDates: Array<string>
+  signature?: string
+  warnings: Array<Diagnostic>
+}
+export interface Form144Issuer {
+  cik: string
+  name: string
+  secFileNumber?: string
+  address?: Form144Address
+  contactPhone?: string
+}
+export interface Form144Seller {
+  name: string
+  relationshipsToIssuer: Array<string>
+}
+export interface Form144Address {
+  street1?: string
+  street2?: string
+  city?: string
+  stateOrCountry?: string
+  zipCode?: string
+}
+export interface Broker {
+  name: string
+  address?: Form144Address
+}
+export interface SecuritiesToBeSold {
+  classTitle: string
+  broker?: Broker
+  unitsToBeSold: number
+  aggregateMarketValue: number
+  unitsOutstanding?: number
+  approximateSaleDate?: string
+  exchange?: string
+}
+export interface SecuritiesAcquired {
+  classTitle: string
+  acquiredDate?: string
+  natureOfAcquisition?: string
+  acquiredFrom?: string
+  isGift?: boolean
+  amountAcquired: number
+  paymentDate?: string
+  natureOfPayment?: string
+}
+export interface PastSale {
+  sellerName?: string
+  sellerAddress?: Form144Address
+  classTitle: string
+  saleDate?: string
+  amountSold: number
+  grossProceeds?: number
+}
+export function parseForm144(form: string, options?: ParseOptions | undefined | null): Form144
+export interface InsiderSummary {
+  ownerCik: string
+  ownerName?: string
+  /** Non-derivative shares bought on the open market (code P). */
+  sharesPurchased: number
+  /** Non-derivative shares sold on the open market (code S). */
+  sharesSold: number
+  netShares: number
+  purchaseValue: number
+  saleValue: number
+  /** Dollar value of all open market purchases and sales. */
+  totalValue: number
+  averagePurchasePrice?: number
+  averageSalePrice?: number
+  /** The last reported non-derivative holding of each security and ownership nature. */
+  sharesOwnedFollowing: Array<SecurityHolding>
+  /** Derivative shares exercised or converted (codes M, X and O). */
+  sharesExercised: number
+  optionExercises: number
+  openMarketSales: number
+  /** Whether the form reports transactions made under a Rule 10b5-1 plan. */
+  aff10B5One: boolean
+}
+export interface SecurityHolding {
+  securityTitle?: string
+  directOrIndirect?: 'D' | 'I'
+  shares: number
+}
+/**
+ * Summarizes the open market activity of a Form 4 for each reporting owner. Ownership forms do
+ * not attribute transactions to individual owners, so joint filers share the same figures.
+ */
+export function summarizeInsiderActivity(form: OwnershipForm): Array<InsiderSummary>
 export interface OwnershipForm {
   schemaVersion?: string
   documentType: string
@@ -159,6 +411,7 @@ export interface OwnershipForm {
   footnotes: Array<Footnote>
   remarks?: string
   ownerSignatures: Array<OwnerSignature>
+  warnings: Array<Diagnostic>
 }
 export interface Issuer {
   cik: string
@@ -199,95 +452,368 @@ export interface DerivativeTable {
   transactions: Array<DerivativeTransaction>
   holdings: Array<DerivativeHolding>
 }
+/** Identifies one of the four row types of an ownership form. */
+export const enum OwnershipTableKind {
+  NonDerivativeTransaction = 'NonDerivativeTransaction',
+  NonDerivativeHolding = 'NonDerivativeHolding',
+  DerivativeTransaction = 'DerivativeTransaction',
+  DerivativeHolding = 'DerivativeHolding'
+}
 export interface NonDerivativeTransaction {
-  securityTitle?: ValueFootnotes
-  transactionDate?: ValueFootnotes
-  deemedExecutionDate?: ValueFootnotes
+  securityTitle?: StringFootnotes
+  transactionDate?: DateFootnotes
+  deemedExecutionDate?: DateFootnotes
   transactionCoding?: TransactionCoding
-  transactionTimeliness?: ValueFootnotes
+  transactionTimeliness?: TimelinessFootnotes
   transactionAmounts?: TransactionAmounts
   postTransactionAmounts?: PostTransactionAmounts
   ownershipNature?: OwnershipNature
 }
 export interface DerivativeTransaction {
-  securityTitle?: ValueFootnotes
-  conversionOrExercisePrice?: ValueFootnotes
-  deemedExecutionDate?: ValueFootnotes
-  transactionDate?: ValueFootnotes
+  securityTitle?: StringFootnotes
+  conversionOrExercisePrice?: DecimalFootnotes
+  deemedExecutionDate?: DateFootnotes
+  transactionDate?: DateFootnotes
   transactionCoding?: TransactionCoding
-  transactionTimeliness?: ValueFootnotes
+  transactionTimeliness?: TimelinessFootnotes
   transactionAmounts?: DerivativeTransactionAmounts
-  exerciseDate?: ValueFootnotes
-  expirationDate?: ValueFootnotes
+  exerciseDate?: DateFootnotes
+  expirationDate?: DateFootnotes
   underlyingSecurity?: UnderlyingSecurity
   postTransactionAmounts?: PostTransactionAmounts
   ownershipNature?: OwnershipNature
 }
 export interface NonDerivativeHolding {
-  securityTitle?: ValueFootnotes
+  securityTitle?: StringFootnotes
   transactionCoding?: HoldingCoding
   postTransactionAmounts?: PostTransactionAmounts
   ownershipNature?: OwnershipNature
 }
 export interface DerivativeHolding {
-  securityTitle?: ValueFootnotes
-  conversionOrExercisePrice?: ValueFootnotes
+  securityTitle?: StringFootnotes
+  conversionOrExercisePrice?: DecimalFootnotes
   transactionCoding?: HoldingCoding
-  exerciseDate?: ValueFootnotes
-  expirationDate?: ValueFootnotes
+  exerciseDate?: DateFootnotes
+  expirationDate?: DateFootnotes
   underlyingSecurity?: UnderlyingSecurity
   postTransactionAmounts?: PostTransactionAmounts
   ownershipNature?: OwnershipNature
 }
 export interface TransactionCoding {
   formType?: string
-  transactionCode?: string
+  transactionCode?: 'P' | 'S' | 'V' | 'A' | 'D' | 'F' | 'I' | 'M' | 'C' | 'E' | 'H' | 'O' | 'X' | 'G' | 'L' | 'W' | 'Z' | 'J' | 'K' | 'U'
   equitySwapInvolved?: boolean
   footnoteIds?: Array<string>
+  footnotes?: Array<string>
 }
 export interface HoldingCoding {
   formType?: string
   footnoteIds?: Array<string>
+  footnotes?: Array<string>
 }
 export interface TransactionAmounts {
-  shares?: ValueFootnotes
-  pricePerShare?: ValueFootnotes
-  acquiredDisposedCode?: ValueFootnotes
+  shares?: DecimalFootnotes
+  pricePerShare?: DecimalFootnotes
+  acquiredDisposedCode?: AcquiredDisposedFootnotes
 }
 export interface DerivativeTransactionAmounts {
-  shares?: ValueFootnotes
-  pricePerShare?: ValueFootnotes
-  totalValue?: ValueFootnotes
-  acquiredDisposedCode?: ValueFootnotes
+  shares?: DecimalFootnotes
+  pricePerShare?: DecimalFootnotes
+  totalValue?: DecimalFootnotes
+  acquiredDisposedCode?: AcquiredDisposedFootnotes
 }
 export interface UnderlyingSecurity {
-  title?: ValueFootnotes
-  shares?: ValueFootnotes
-  value?: ValueFootnotes
+  title?: StringFootnotes
+  shares?: DecimalFootnotes
+  value?: DecimalFootnotes
 }
 export interface PostTransactionAmounts {
-  sharesOwnedFollowingTransaction?: ValueFootnotes
-  valueOwnedFollowingTransaction?: ValueFootnotes
+  sharesOwnedFollowingTransaction?: DecimalFootnotes
+  valueOwnedFollowingTransaction?: DecimalFootnotes
 }
 export interface OwnershipNature {
-  directOrIndirectOwnership?: ValueFootnotes
-  natureOfOwnership?: ValueFootnotes
+  directOrIndirectOwnership?: DirectOrIndirectFootnotes
+  natureOfOwnership?: StringFootnotes
 }
 export interface Footnote {
   id?: string
   note?: string
+  /** Paths of the fields annotated by this footnote, set when resolving footnotes. */
+  references?: Array<string>
 }
 export interface OwnerSignature {
   name: string
   date: string
 }
-export interface ValueFootnotes {
-  value?: unknown
+/**
+ * A numeric value such as a share count or price. `value` is absent when the filing gives only
+ * footnotes in its place.
+ */
+export interface DecimalFootnotes {
+  value?: number
+  footnoteIds?: Array<string>
+  /** Texts of the referenced footnotes, set when resolving footnotes. */
+  footnotes?: Array<string>
+}
+/** A `YYYY-MM-DD` date. `value` is absent when the filing gives only footnotes in its place. */
+export interface DateFootnotes {
+  value?: string
+  footnoteIds?: Array<string>
+  /** Texts of the referenced footnotes, set when resolving footnotes. */
+  footnotes?: Array<string>
+}
+/**
+ * A text value such as a security title. `value` is absent when the filing gives only footnotes
+ * in its place.
+ */
+export interface StringFootnotes {
+  value?: string
+  footnoteIds?: Array<string>
+  /** Texts of the referenced footnotes, set when resolving footnotes. */
+  footnotes?: Array<string>
+}
+export interface AcquiredDisposedFootnotes {
+  value?: 'A' | 'D'
+  footnoteIds?: Array<string>
+  footnotes?: Array<string>
+}
+export interface DirectOrIndirectFootnotes {
+  value?: 'D' | 'I'
+  footnoteIds?: Array<string>
+  footnotes?: Array<string>
+}
+export interface TimelinessFootnotes {
+  value?: 'E' | 'L'
   footnoteIds?: Array<string>
+  footnotes?: Array<string>
 }
-export function parseOwnershipForm(form: string): OwnershipForm
+/** Returns the Form 4 description of a Section 16 transaction code such as `P` or `S`. */
+export function describeTransactionCode(code: string): string | null
+export function parseOwnershipForm(form: string, options?: ParseOptions | undefined | null): OwnershipForm
+export interface OwnershipAmendment {
+  nonDerivativeTable?: NonDerivativeTable
+  derivativeTable?: DerivativeTable
+  changes: Array<RowChange>
+}
+export interface RowChange {
+  kind: RowChangeKind
+  table: OwnershipTableKind
+  /** Index of the row in the original form, absent for added rows. */
+  originalIndex?: number
+  /** Index of the row in the amended form, absent for removed rows. */
+  amendedIndex?: number
+}
+export const enum RowChangeKind {
+  Added = 'Added',
+  Changed = 'Changed',
+  Removed = 'Removed'
+}
+/**
+ * Applies a Form 3/4/5 amendment to the original form. Amendments restate the complete form, so
+ * the amended tables are the effective ones; rows are matched to the original by security,
+ * date, code and ownership nature to report what the amendment added, changed or removed.
+ */
+export function applyOwnershipAmendment(original: OwnershipForm, amended: OwnershipForm): OwnershipAmendment
+/** A transaction or holding with its issuer and owner, flattened for tabular storage. */
+export interface OwnershipRow {
+  issuerCik: string
+  issuerName?: string
+  issuerTradingSymbol: string
+  ownerCik?: string
+  ownerName?: string
+  /**
+   * Whether the owner is the designated filer; keep only these rows to count each transaction
+   * of a joint filing once.
+   */
+  isDesignatedFiler: boolean
+  table: OwnershipTableKind
+  securityTitle?: string
+  transactionDate?: string
+  transactionCode?: 'P' | 'S' | 'V' | 'A' | 'D' | 'F' | 'I' | 'M' | 'C' | 'E' | 'H' | 'O' | 'X' | 'G' | 'L' | 'W' | 'Z' | 'J' | 'K' | 'U'
+  shares?: number
+  pricePerShare?: number
+  acquiredDisposed?: 'A' | 'D'
+  sharesOwnedFollowing?: number
+  directOrIndirect?: 'D' | 'I'
+  natureOfOwnership?: string
+  /** Texts of every footnote referenced by the row, as `F1: text; F2: text`. */
+  footnotes?: string
+}
+/**
+ * Emits one row per reporting owner and transaction or holding, as attributed by
+ * `attributeOwners`. Each owner's rows come together, non-derivative rows first, in filing
+ * order.
+ */
+export function flattenOwnershipForm(form: OwnershipForm): Array<OwnershipRow>
+/** Writes rows as RFC 4180 CSV with a header line. */
+export function ownershipRowsToCsv(rows: Array<OwnershipRow>): string
+export interface PositionLedger {
+  issuerCik: string
+  ownerCik: string
+  /** One entry per transaction and holding, in filing order. */
+  entries: Array<LedgerEntry>
+  /** Holdings after the last form, one per security and ownership nature. */
+  positions: Array<Position>
+  discrepancyCount: number
+}
+export interface LedgerEntry {
+  /** Index of the form in the input sequence. */
+  formIndex: number
+  periodOfReport: string
+  table: OwnershipTableKind
+  /** Index of the row within its table. */
+  rowIndex: number
+  transactionDate?: string
+  transactionCode?: 'P' | 'S' | 'V' | 'A' | 'D' | 'F' | 'I' | 'M' | 'C' | 'E' | 'H' | 'O' | 'X' | 'G' | 'L' | 'W' | 'Z' | 'J' | 'K' | 'U'
+  securityTitle: string
+  directOrIndirect?: 'D' | 'I'
+  natureOfOwnership?: string
+  /** Signed share change, absent for holdings. */
+  change?: number
+  /**
+   * Running holdings plus the change, absent for the first observation of a position and for
+   * transactions whose change is unknown.
+   */
+  expectedShares?: number
+  /** `sharesOwnedFollowingTransaction` as reported. */
+  reportedShares?: number
+  /** Holdings after the row; the reported amount when given, otherwise the expected one. */
+  shares?: number
+  /** Reported minus expected holdings when they disagree. */
+  discrepancy?: number
+}
+export interface Position {
+  securityTitle: string
+  derivative: boolean
+  directOrIndirect?: 'D' | 'I'
+  natureOfOwnership?: string
+  /** Derivative positions with different terms are tracked apart. */
+  conversionOrExercisePrice?: number
+  expirationDate?: string
+  shares?: number
+  periodOfReport: string
+}
+/**
+ * Replays the forms of one issuer and reporting owner in filing order, tracking holdings per
+ * security, ownership nature and, for derivatives, exercise price and expiration, and checking each reported post-transaction amount against the
+ * running total. The owner defaults to the one reporting owner every form lists. An amendment
+ * restates its form, so it replaces the latest earlier form of the same type and period.
+ */
+export function buildPositionLedger(forms: Array<OwnershipForm>, ownerCik?: string | undefined | null): PositionLedger
+export interface OwnerAttribution {
+  /** The first listed owner, which EDGAR uses for the designated filer of a joint filing. */
+  designatedFiler?: ReportingOwnerID
+  directors: Array<ReportingOwnerID>
+  officers: Array<ReportingOwnerID>
+  tenPercentOwners: Array<ReportingOwnerID>
+  others: Array<ReportingOwnerID>
+  /** One row per reporting owner and transaction, in owner then filing order. */
+  transactions: Array<OwnerTransaction>
+}
+export interface OwnerTransaction {
+  ownerCik: string
+  ownerName?: string
+  isDesignatedFiler: boolean
+  table: OwnershipTableKind
+  /** Index of the transaction within its table. */
+  index: number
+  securityTitle?: string
+  transactionDate?: string
+  transactionCode?: 'P' | 'S' | 'V' | 'A' | 'D' | 'F' | 'I' | 'M' | 'C' | 'E' | 'H' | 'O' | 'X' | 'G' | 'L' | 'W' | 'Z' | 'J' | 'K' | 'U'
+  shares?: number
+  pricePerShare?: number
+  acquiredDisposed?: 'A' | 'D'
+  sharesOwnedFollowing?: number
+  directOrIndirect?: 'D' | 'I'
+}
+/**
+ * Attributes the transactions of a joint filing to each reporting owner and groups the owners by
+ * their relationship to the issuer. An owner with several relationships appears in each group.
+ */
+export function attributeOwners(form: OwnershipForm): OwnerAttribution
+export interface BeneficialOwnershipReport {
+  submissionType: string
+  filerCik?: string
+  amendmentNo?: number
+  securitiesClassTitle?: string
+  /** Date of the event which requires filing of the statement. */
+  eventDate?: string
+  issuer: ScheduleIssuer
+  reportingPersons: Array<ReportingPerson>
+  items: Array<ItemResponse>
+  warnings: Array<Diagnostic>
+}
+export interface ScheduleIssuer {
+  cik: string
+  name: string
+  cusip?: string
+}
+/** The cover page filed for each reporting person. */
+export interface ReportingPerson {
+  cik?: string
+  name: string
+  citizenshipOrOrganization?: string
+  soleVotingPower?: number
+  sharedVotingPower?: number
+  soleDispositivePower?: number
+  sharedDispositivePower?: number
+  aggregateAmountOwned?: number
+  percentOfClass?: number
+  /** Codes such as `IN`, `IA` or `CO`. */
+  typeOfReportingPerson: Array<string>
+}
+export interface ItemResponse {
+  /** Item number as printed on the schedule, e.g. `4` for the purpose of the transaction. */
+  number: number
+  fields: Array<ItemField>
+}
+export interface ItemField {
+  /** Path of the element relative to the item, e.g. `numberOfSharesPersonHas/solePowerOrDirectToVote`. */
+  path: string
+  value: string
+}
+export function parseSchedule13D(schedule: string, options?: ParseOptions | undefined | null): BeneficialOwnershipReport
+export function parseSchedule13G(schedule: string, options?: ParseOptions | undefined | null): BeneficialOwnershipReport
+/** Identifiers derived from an information table entry. */
+export interface SecurityIdentifiers {
+  cusip: string
+  /** Whether the CUSIP is well formed and its check digit matches. */
+  cusipValid: boolean
+  /** Absent when the entry has no FIGI. */
+  figiValid?: boolean
+  /** The first six characters of a valid CUSIP, identifying the issuer. */
+  issuerCusip?: string
+  /** The US ISIN, absent for CINS numbers, which identify issuers outside North America. */
+  isin?: string
+  /** From the lookup table, when one is given. */
+  ticker?: string
+}
+/**
+ * Validates and derives identifiers for each entry, in table order. `tickers` maps CUSIPs to
+ * tickers, for example as read by `parseTickerCsv`.
+ */
+export function enrichForm13FTable(table: Form13FTable, tickers?: Record<string, string> | undefined | null): Array<SecurityIdentifiers>
+/** Checks the length, character set and check digit of a CUSIP. */
+export function validateCusip(cusip: string): boolean
+/** Checks the structure and check digit of a Financial Instrument Global Identifier. */
+export function validateFigi(figi: string): boolean
+/** The six-character issuer number of a valid CUSIP. */
+export function issuerCusip(cusip: string): string | null
+/**
+ * Builds the US ISIN of a valid CUSIP. Canadian issuers, whose ISINs start with `CA`, cannot be
+ * told apart by CUSIP alone. CUSIPs using `*`, `@` or `#`, which are reserved for private
+ * placements and have no ISIN form, give none.
+ */
+export function cusipToIsin(cusip: string): string | null
+/**
+ * Reads a CUSIP to ticker table from CSV with the CUSIP in the first column and the ticker in
+ * the second. A header row and further columns are ignored. Fields may be quoted, with `""`
+ * standing for a quote inside a quoted field; records spanning lines are not supported.
+ */
+export function parseTickerCsv(csv: string): Record<string, string>
 export interface Xbrl {
   facts: Array<Fact>
+  warnings: Array<Diagnostic>
 }
 export interface Fact {
   context: Context
@@ -297,8 +823,19 @@ export interface Fact {
   unit?: string
 }
 export interface Context {
+  /**
+   * Canonical key built from the entity, period, sorted dimensions and sorted non-dimensional
+   * segment and scenario content, stable across filings.
+   */
+  key: string
   entity: string
+  entityScheme?: string
   segments: Array<Segment>
+  /** Raw XML of segment children that are not dimension members. */
+  segmentContent: Array<string>
+  scenarios: Array<Segment>
+  /** Raw XML of scenario children that are not dimension members. */
+  scenarioContent: Array<string>
   period: Period
 }
 export interface Segment {
@@ -310,4 +847,28 @@ export interface Period {
   startDate?: string
   endDate?: string
 }
-export function parseXbrl(xbrl: string): Xbrl
+export function parseXbrl(xbrl: string, options?: ParseOptions | undefined | null): Xbrl
+export const enum ParseMode {
+  /** Fail on the first malformed or unknown element, or any other value that would be dropped. */
+  Strict = 'Strict',
+  /**
+   * Skip malformed rows, default missing values and report them in the result's warnings.
+   * Only XML syntax errors and missing document sections still fail. This is the default.
+   */
+  Lenient = 'Lenient'
+}
+export interface ParseOptions {
+  mode?: ParseMode
+  /**
+   * Ownership forms only: attach footnote texts to the values that reference them, and list
+   * the fields each footnote annotates.
+   */
+  resolveFootnotes?: boolean
+}
+export interface Diagnostic {
+  code: string
+  path?: string
+  message: string
+  line: number
+  column: number
+}
@@ -295,9 +295,37 @@ if (!nativeBinding) {
   throw new Error(`Failed to load native binding`)
 }
 
-const { parseForm13F, parseForm13FTable, parseOwnershipForm, parseXbrl } = nativeBinding
+const { isForm13FAmendment, parseForm13F, parseForm13FTable, aggregateForm13FTable, applyForm13FAmendment, HoldingChangeKind, diffForm13FTables, DiscrepancyKind, validateForm13FTable, ValueFlagKind, normalizeForm13FValues, parseForm144, summarizeInsiderActivity, OwnershipTableKind, describeTransactionCode, parseOwnershipForm, RowChangeKind, applyOwnershipAmendment, flattenOwnershipForm, ownershipRowsToCsv, buildPositionLedger, attributeOwners, parseSchedule13D, parseSchedule13G, enrichForm13FTable, validateCusip, validateFigi, issuerCusip, cusipToIsin, parseTickerCsv, parseXbrl, ParseMode } = nativeBinding
 
+module.exports.isForm13FAmendment = isForm13FAmendment
 module.exports.parseForm13F = parseForm13F
 module.exports.parseForm13FTable = parseForm13FTable
+module.exports.aggregateForm13FTable = aggregateForm13FTable
+module.exports.applyForm13FAmendment = applyForm13FAmendment
+module.exports.HoldingChangeKind = HoldingChangeKind
+module.exports.diffForm13FTables = diffForm13FTables
+module.exports.DiscrepancyKind = DiscrepancyKind
+module.exports.validateForm13FTable = validateForm13FTable
+module.exports.ValueFlagKind = ValueFlagKind
+module.exports.normalizeForm13FValues = normalizeForm13FValues
+module.exports.parseForm144 = parseForm144
+module.exports.summarizeInsiderActivity = summarizeInsiderActivity
+module.exports.OwnershipTableKind = OwnershipTableKind
+module.exports.describeTransactionCode = describeTransactionCode
 module.exports.parseOwnershipForm = parseOwnershipForm
+module.exports.RowChangeKind = RowChangeKind
+module.exports.applyOwnershipAmendment = applyOwnershipAmendment
+module.exports.flattenOwnershipForm = flattenOwnershipForm
+module.exports.ownershipRowsToCsv = ownershipRowsToCsv
+module.exports.buildPositionLedger = buildPositionLedger
+module.exports.attributeOwners = attributeOwners
+module.exports.parseSchedule13D = parseSchedule13D
+module.exports.parseSchedule13G = parseSchedule13G
+module.exports.enrichForm13FTable = enrichForm13FTable
+module.exports.validateCusip = validateCusip
+module.exports.validateFigi = validateFigi
+module.exports.issuerCusip = issuerCusip
+module.exports.cusipToIsin = cusipToIsin
+module.exports.parseTickerCsv = parseTickerCsv
 module.exports.parseXbrl = parseXbrl
+module.exports.ParseMode = ParseMode
@@ -0,0 +1,171 @@
+use napi::{Env, Error, JsUnknown};
+use roxmltree::{Node, TextPos};
+use std::fmt;
+
+#[derive(Debug, Clone)]
+pub enum ParseError {
+  /// The document is not well-formed XML.
+  XmlSyntax {
+    message: String,
+    line: u32,
+    column: u32,
+  },
+  /// A required element or attribute is absent.
+  MissingElement {
+    path: String,
+    line: u32,
+    column: u32,
+  },
+  /// An element the parser does not read, whose content would be dropped.
+  UnknownElement {
+    path: String,
+    line: u32,
+    column: u32,
+  },
+  /// An element is present but its text cannot be converted to the expected type.
+  InvalidValue {
+    path: String,
+    value: String,
+    reason: String,
+    line: u32,
+    column: u32,
+  },
+}
+
+impl ParseError {
+  pub fn missing(parent: &Node, tag: &str) -> Self {
+    let TextPos { row, col } = node_pos(parent);
+    ParseError::MissingElement {
+      path: format!("{}/{}", node_path(parent), tag),
+      line: row,
+      column: col,
+    }
+  }
+
+  pub fn missing_attribute(node: &Node, attribute: &str) -> Self {
+    Self::missing(node, &format!("@{}", attribute))
+  }
+
+  pub fn unknown(node: &Node) -> Self {
+    let TextPos { row, col } = node_pos(node);
+    ParseError::UnknownElement {
+      path: node_path(node),
+      line: row,
+      column: col,
+    }
+  }
+
+  pub fn invalid(node: &Node, value: &str, reason: String) -> Self {
+    let TextPos { row, col } = node_pos(node);
+    ParseError::InvalidValue {
+      path: node_path(node),
+      value: value.to_owned(),
+      reason,
+      line: row,
+      column: col,
+    }
+  }
+
+  pub fn code(&self) -> &'static str {
+    match self {
+      ParseError::XmlSyntax { .. } => "XML_SYNTAX",
+      ParseError::MissingElement { .. } => "MISSING_ELEMENT",
+      ParseError::UnknownElement { .. } => "UNKNOWN_ELEMENT",
+      ParseError::InvalidValue { .. } => "INVALID_VALUE",
+    }
+  }
+
+  pub fn path(&self) -> Option<&str> {
+    match self {
+      ParseError::XmlSyntax { .. } => None,
+      ParseError::MissingElement { path, .. }
+      | ParseError::UnknownElement { path, .. }
+      | ParseError::InvalidValue { path, .. } => Some(path),
+    }
+  }
+
+  pub fn position(&self) -> (u32, u32) {
+    match self {
+      ParseError::XmlSyntax { line, column, .. }
+      | ParseError::MissingElement { line, column, .. }
+      | ParseError::UnknownElement { line, column, .. }
+      | ParseError::InvalidValue { line, column, .. } => (*line, *column),
+    }
+  }
+
+  /// Converts the error into a JS `Error` carrying `code`, `path`, `line` and `column` properties.
+  pub fn into_napi(self, env: Env) -> Error {
+    match self.to_js(env) {
+      Ok(js_error) => Error::from(js_error),
+      Err(_) => Error::from_reason(self.to_string()),
+    }
+  }
+
+  fn to_js(&self, env: Env) -> napi::Result<JsUnknown> {
+    let (line, column) = self.position();
+    let mut js_error = env.create_error(Error::from_reason(self.to_string()))?;
+    js_error.set_named_property("code", env.create_string(self.code())?)?;
+    if let Some(path) = self.path() {
+      js_error.set_named_property("path", env.create_string(path)?)?;
+    }
+    js_error.set_named_property("line", env.create_uint32(line)?)?;
+    js_error.set_named_property("column", env.create_uint32(column)?)?;
+    Ok(js_error.into_unknown())
+  }
+}
+
+impl fmt::Display for ParseError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      ParseError::XmlSyntax { message, .. } => write!(f, "{}", message),
+      ParseError::MissingElement { path, .. } => write!(f, "{} not found", path),
+      ParseError::UnknownElement { path, .. } => write!(f, "unknown element {}", path),
+      ParseError::InvalidValue {
+        path,
+        value,
+        reason,
+        ..
+      } => write!(f, "invalid value \"{}\" at {}: {}", value, path, reason),
+    }
+  }
+}
+
+impl From<roxmltree::Error> for ParseError {
+  fn from(err: roxmltree::Error) -> Self {
+    let TextPos { row, col } = err.pos();
+    ParseError::XmlSyntax {
+      message: err.to_string(),
+      line: row,
+      column: col,
+    }
+  }
+}
+
+/// Builds an XPath-like path such as `ownershipDocument/issuer/issuerCik`, adding a 1-based
+/// index to elements that repeat under the same parent.
+pub fn node_path(node: &Node) -> String {
+  let mut segments: Vec<String> = node
+    .ancestors()
+    .filter(|node| node.is_element())
+    .map(|node| {
+      let name = node.tag_name().name();
+      let mut siblings = node
+        .parent()
+        .into_iter()
+        .flat_map(|parent| parent.children())
+        .filter(|sibling| sibling.is_element() && sibling.tag_name() == node.tag_name());
+      let index = siblings.position(|sibling| sibling == node).unwrap_or(0);
+      if index > 0 || siblings.next().is_some() {
+        format!("{}[{}]", name, index + 1)
+      } else {
+        name.to_owned()
+      }
+    })
+    .collect();
+  segments.reverse();
+  segments.join("/")
+}
+
+fn node_pos(node: &Node) -> TextPos {
+  node.document().text_pos_at(node.range().start)
+}
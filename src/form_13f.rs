@@ -1,29 +1,39 @@
-use napi::Error;
+use napi::{Env, Error};
 use napi_derive::napi;
 use roxmltree::{Document as XMLDoc, Node};
 
-use crate::{parse_ints, parse_string};
+use crate::error::ParseError;
+use crate::{
+  find_required, parse_field, parse_ints, parse_optional, parse_recoverable, parse_required,
+  parse_rows, parse_string, Diagnostic, Diagnostics, ParseOptions, UsDate,
+};
 
 #[napi(object)]
 pub struct Form13F {
   pub schema_version: Option<String>,
   pub header_data: HeaderData,
   pub form_data: FormData,
+  pub warnings: Vec<Diagnostic>,
 }
 
 #[napi(object)]
 pub struct HeaderData {
-  pub submission_type: String,
+  /// Unset when lenient parsing meets an invalid submission type.
+  #[napi(ts_type = "'13F-HR' | '13F-HR/A' | '13F-NT' | '13F-NT/A' | '13F-CTR' | '13F-CTR/A'")]
+  pub submission_type: Option<Form13FSubmissionType>,
   pub filer_info: FilerInfo,
 }
 
 #[napi(object)]
 pub struct FilerInfo {
-  pub live_test_flag: String,
+  /// Unset when lenient parsing meets an invalid flag.
+  #[napi(ts_type = "'LIVE' | 'TEST'")]
+  pub live_test_flag: Option<LiveTestFlag>,
   pub flags: Option<Flags>,
   pub filer: Filer,
   pub contact: Option<Contact>,
   pub notifications: Option<Notifications>,
+  /// A `YYYY-MM-DD` date.
   pub period_of_report: String,
   pub denovo_request: Option<bool>,
 }
@@ -56,7 +66,7 @@ pub struct Contact {
 
 #[napi(object)]
 pub struct Notifications {
-  pub email_address: Option<String>,
+  pub email_address: Vec<String>,
 }
 
 #[napi(object)]
@@ -69,12 +79,15 @@ pub struct FormData {
 
 #[napi(object)]
 pub struct CoverPage {
+  /// The `YYYY-MM-DD` end of the calendar year or quarter the report covers.
   pub report_calendar_or_quarter: String,
   pub is_amendment: Option<bool>,
   pub amendment_number: Option<i32>,
   pub amendment_info: Option<AmendmentInfo>,
   pub filing_manager: FilingManager,
-  pub report_type: String,
+  /// Unset when lenient parsing meets an invalid report type.
+  #[napi(ts_type = "'13F HOLDINGS REPORT' | '13F NOTICE' | '13F COMBINATION REPORT'")]
+  pub report_type: Option<ReportType>,
   pub form_13f_file_number: Option<String>,
   pub crd_number: Option<i32>,
   pub sec_file_number: Option<String>,
@@ -85,9 +98,13 @@ pub struct CoverPage {
 
 #[napi(object)]
 pub struct AmendmentInfo {
-  pub amendment_type: Option<String>,
+  #[napi(ts_type = "'RESTATEMENT' | 'NEW HOLDINGS'")]
+  pub amendment_type: Option<AmendmentType>,
+  /// Whether the amendment reports holdings whose confidential treatment was denied or expired.
   pub conf_denied_expired: Option<bool>,
-  pub data_denied_expired: Option<String>,
+  /// A `YYYY-MM-DD` date.
+  pub date_denied_expired: Option<String>,
+  /// A `YYYY-MM-DD` date.
   pub date_reported: Option<String>,
   pub reason_for_non_confidentiality: Option<String>,
 }
@@ -109,7 +126,8 @@ pub struct Address {
 
 #[napi(object)]
 pub struct OtherManagersInfo {
-  pub other_manager: Option<OtherManager>,
+  /// Managers reporting on the filer's behalf, as listed on a 13F notice.
+  pub other_manager: Vec<OtherManager>,
 }
 
 #[napi(object)]
@@ -129,6 +147,7 @@ pub struct SignatureBlock {
   pub signature: String,
   pub city: String,
   pub state_or_country: String,
+  /// A `YYYY-MM-DD` date.
   pub signature_date: String,
 }
 
@@ -158,6 +177,7 @@ pub struct OtherDocument {
 #[napi(object)]
 pub struct Form13FTable {
   pub entries: Vec<TableEntry>,
+  pub warnings: Vec<Diagnostic>,
 }
 
 #[napi(object)]
@@ -168,8 +188,11 @@ pub struct TableEntry {
   pub figi: Option<String>,
   pub value: i64,
   pub shares_or_print_amount: SharesOrPrintAmount,
-  pub put_call: Option<String>,
-  pub investment_discretion: String,
+  #[napi(ts_type = "'Put' | 'Call'")]
+  pub put_call: Option<PutCall>,
+  /// Unset when lenient parsing meets an invalid discretion code.
+  #[napi(ts_type = "'SOLE' | 'DFND' | 'OTR'")]
+  pub investment_discretion: Option<InvestmentDiscretion>,
   pub other_manager: Option<Vec<i32>>,
   pub voting_authority: VotingAuthority,
 }
@@ -177,84 +200,259 @@ pub struct TableEntry {
 #[napi(object)]
 pub struct SharesOrPrintAmount {
   pub amount: i64,
-  pub shares_or_print_type: String,
+  /// Unset when lenient parsing meets an invalid amount type.
+  #[napi(ts_type = "'SH' | 'PRN'")]
+  pub shares_or_print_type: Option<SharesOrPrintType>,
 }
 
 #[napi(object)]
 pub struct VotingAuthority {
-  pub sole: i32,
-  pub shared: i32,
-  pub none: i32,
+  pub sole: i64,
+  pub shared: i64,
+  pub none: i64,
+}
+
+string_union! {
+  pub enum Form13FSubmissionType {
+    HoldingsReport = "13F-HR",
+    HoldingsReportAmendment = "13F-HR/A",
+    Notice = "13F-NT",
+    NoticeAmendment = "13F-NT/A",
+    ConfidentialTreatmentRequest = "13F-CTR",
+    ConfidentialTreatmentRequestAmendment = "13F-CTR/A",
+  }
+}
+
+impl Form13FSubmissionType {
+  pub fn is_amendment(&self) -> bool {
+    matches!(
+      self,
+      Form13FSubmissionType::HoldingsReportAmendment
+        | Form13FSubmissionType::NoticeAmendment
+        | Form13FSubmissionType::ConfidentialTreatmentRequestAmendment
+    )
+  }
+}
+
+string_union! {
+  pub enum LiveTestFlag {
+    Live = "LIVE",
+    Test = "TEST",
+  }
+}
+
+string_union! {
+  pub enum ReportType {
+    HoldingsReport = "13F HOLDINGS REPORT",
+    Notice = "13F NOTICE",
+    CombinationReport = "13F COMBINATION REPORT",
+  }
+}
+
+string_union! {
+  /// Whether an amendment replaces the original holdings or adds to them.
+  pub enum AmendmentType {
+    Restatement = "RESTATEMENT",
+    NewHoldings = "NEW HOLDINGS",
+  }
+}
+
+string_union! {
+  pub enum InvestmentDiscretion {
+    Sole = "SOLE",
+    Defined = "DFND",
+    Other = "OTR",
+  }
+}
+
+string_union! {
+  pub enum PutCall {
+    Put = "Put",
+    Call = "Call",
+  }
+}
+
+string_union! {
+  /// Whether an amount is a number of shares or a principal amount.
+  pub enum SharesOrPrintType {
+    Shares = "SH",
+    Principal = "PRN",
+  }
+}
+
+const FILER_INFO_ELEMENTS: &[&str] = &[
+  "liveTestFlag",
+  "flags",
+  "filer",
+  "contact",
+  "notifications",
+  "periodOfReport",
+  "denovoRequest",
+];
+
+const FLAGS_ELEMENTS: &[&str] = &[
+  "confirmingCopyFlag",
+  "returnCopyFlag",
+  "overrideInternetFlag",
+];
+
+const CONTACT_ELEMENTS: &[&str] = &["contactName", "contactPhoneNumber", "contactEmailAddress"];
+
+const FORM_DATA_ELEMENTS: &[&str] = &["coverPage", "signatureBlock", "summaryPage", "documents"];
+
+const COVER_PAGE_ELEMENTS: &[&str] = &[
+  "reportCalendarOrQuarter",
+  "isAmendment",
+  "amendmentNo",
+  "amendmentInfo",
+  "filingManager",
+  "reportType",
+  "form13FFileNumber",
+  "crdNumber",
+  "secFileNumber",
+  "otherManagersInfo",
+  "provideInfoForInstruction5",
+  "additionalInformation",
+];
+
+const AMENDMENT_INFO_ELEMENTS: &[&str] = &[
+  "amendmentType",
+  "confDeniedExpired",
+  "dateDeniedExpired",
+  "dateReported",
+  "reasonForNonConfidentiality",
+];
+
+const ADDRESS_ELEMENTS: &[&str] = &["street1", "street2", "city", "stateOrCountry", "zipCode"];
+
+const OTHER_MANAGER_ELEMENTS: &[&str] = &[
+  "cik",
+  "form13FFileNumber",
+  "crdNumber",
+  "secFileNumber",
+  "name",
+];
+
+const SIGNATURE_BLOCK_ELEMENTS: &[&str] = &[
+  "name",
+  "title",
+  "phone",
+  "signature",
+  "city",
+  "stateOrCountry",
+  "signatureDate",
+];
+
+const SUMMARY_PAGE_ELEMENTS: &[&str] = &[
+  "otherIncludedManagersCount",
+  "tableEntryTotal",
+  "tableValueTotal",
+  "isConfidentialOmitted",
+  "otherManagers2Info",
+];
+
+const DOCUMENT_ELEMENTS: &[&str] = &[
+  "conformedName",
+  "conformedDocumentType",
+  "description",
+  "contents",
+];
+
+const INFO_TABLE_ELEMENTS: &[&str] = &[
+  "nameOfIssuer",
+  "titleOfClass",
+  "cusip",
+  "figi",
+  "value",
+  "shrsOrPrnAmt",
+  "putCall",
+  "investmentDiscretion",
+  "otherManager",
+  "votingAuthority",
+];
+
+/// Whether a 13F submission type such as `13F-HR/A` is an amendment.
+#[napi]
+pub fn is_form13f_amendment(submission_type: String) -> bool {
+  submission_type
+    .trim()
+    .parse::<Form13FSubmissionType>()
+    .is_ok_and(|submission_type| submission_type.is_amendment())
 }
 
 #[napi]
-pub fn parse_form13f(form: String) -> Result<Form13F, Error> {
-  let doc = XMLDoc::parse(&form).map_err(|e| Error::from_reason(e.to_string()))?;
+pub fn parse_form13f(
+  env: Env,
+  form: String,
+  options: Option<ParseOptions>,
+) -> Result<Form13F, Error> {
+  let doc = XMLDoc::parse(&form).map_err(|e| ParseError::from(e).into_napi(env))?;
   let root_node = doc.root_element();
+  let diagnostics = &Diagnostics::new(ParseOptions::mode(&options));
+  diagnostics
+    .unknown_elements(&root_node, &["schemaVersion", "headerData", "formData"])
+    .map_err(|e| e.into_napi(env))?;
   let schema_version = parse_string::<String>(&root_node, "schemaVersion");
-  let header_data = parse_header_data(&root_node).map_err(|e| Error::from_reason(e))?;
-  let form_data = parse_form_data(&root_node).map_err(|e| Error::from_reason(e))?;
+  let header_data = parse_header_data(&root_node, diagnostics).map_err(|e| e.into_napi(env))?;
+  let form_data = parse_form_data(&root_node, diagnostics).map_err(|e| e.into_napi(env))?;
 
   Ok(Form13F {
     schema_version,
     header_data,
     form_data,
+    warnings: diagnostics.take_warnings(),
   })
 }
 
-fn parse_header_data(node: &Node) -> Result<HeaderData, String> {
-  node
-    .children()
-    .find(|node| node.has_tag_name("headerData"))
-    .ok_or("headerData not found".to_string())
-    .and_then(|header_data_node| {
-      let submission_type = parse_string::<String>(&header_data_node, "submissionType")
-        .ok_or("submissionType not found".to_string())?;
-      let filer_info = parse_filer_info(&header_data_node)?;
-
-      Ok(HeaderData {
-        submission_type,
-        filer_info,
-      })
+fn parse_header_data(node: &Node, diagnostics: &Diagnostics) -> Result<HeaderData, ParseError> {
+  find_required(node, "headerData").and_then(|header_data_node| {
+    diagnostics.unknown_elements(&header_data_node, &["submissionType", "filerInfo"])?;
+    let submission_type =
+      parse_recoverable::<Form13FSubmissionType>(&header_data_node, "submissionType", diagnostics)?;
+    let filer_info = parse_filer_info(&header_data_node, diagnostics)?;
+
+    Ok(HeaderData {
+      submission_type,
+      filer_info,
     })
+  })
 }
 
-fn parse_filer_info(node: &Node) -> Result<FilerInfo, String> {
-  node
-    .children()
-    .find(|node| node.has_tag_name("filerInfo"))
-    .ok_or("filerInfo not found".to_string())
-    .and_then(|filer_info_node| {
-      let live_test_flag = parse_string::<String>(&filer_info_node, "liveTestFlag")
-        .ok_or("liveTestFlag not found".to_string())?;
-      let flags = parse_flags(&filer_info_node)?;
-      let filer = parse_filer(&filer_info_node)?;
-      let contact = parse_contact(&filer_info_node)?;
-      let notifications = parse_notifications(&filer_info_node)?;
-      let period_of_report = parse_string::<String>(&filer_info_node, "periodOfReport")
-        .ok_or("periodOfReport not found".to_string())?;
-      let denovo_request = parse_string::<bool>(&filer_info_node, "denovoRequest");
-
-      Ok(FilerInfo {
-        live_test_flag,
-        flags,
-        filer,
-        contact,
-        notifications,
-        period_of_report,
-        denovo_request,
-      })
+fn parse_filer_info(node: &Node, diagnostics: &Diagnostics) -> Result<FilerInfo, ParseError> {
+  find_required(node, "filerInfo").and_then(|filer_info_node| {
+    diagnostics.unknown_elements(&filer_info_node, FILER_INFO_ELEMENTS)?;
+    let live_test_flag =
+      parse_recoverable::<LiveTestFlag>(&filer_info_node, "liveTestFlag", diagnostics)?;
+    let flags = parse_flags(&filer_info_node, diagnostics)?;
+    let filer = parse_filer(&filer_info_node, diagnostics)?;
+    let contact = parse_contact(&filer_info_node, diagnostics)?;
+    let notifications = parse_notifications(&filer_info_node, diagnostics)?;
+    let period_of_report = parse_field::<UsDate>(&filer_info_node, "periodOfReport", diagnostics)?;
+    let denovo_request = parse_optional::<bool>(&filer_info_node, "denovoRequest", diagnostics)?;
+
+    Ok(FilerInfo {
+      live_test_flag,
+      flags,
+      filer,
+      contact,
+      notifications,
+      period_of_report,
+      denovo_request,
     })
+  })
 }
 
-fn parse_flags(node: &Node) -> Result<Option<Flags>, String> {
+fn parse_flags(node: &Node, diagnostics: &Diagnostics) -> Result<Option<Flags>, ParseError> {
   node
     .children()
     .find(|node| node.has_tag_name("flags"))
     .map(|flags_node| {
-      let confirming_copy_flag = parse_string::<bool>(&flags_node, "confirmingCopyFlag");
-      let return_copy_flag = parse_string::<bool>(&flags_node, "returnCopyFlag");
-      let override_internet_flag = parse_string::<bool>(&flags_node, "overrideInternetFlag");
+      diagnostics.unknown_elements(&flags_node, FLAGS_ELEMENTS)?;
+      let confirming_copy_flag =
+        parse_optional::<bool>(&flags_node, "confirmingCopyFlag", diagnostics)?;
+      let return_copy_flag = parse_optional::<bool>(&flags_node, "returnCopyFlag", diagnostics)?;
+      let override_internet_flag =
+        parse_optional::<bool>(&flags_node, "overrideInternetFlag", diagnostics)?;
 
       Ok(Flags {
         confirming_copy_flag,
@@ -265,42 +463,33 @@ fn parse_flags(node: &Node) -> Result<Option<Flags>, String> {
     .transpose()
 }
 
-fn parse_filer(node: &Node) -> Result<Filer, String> {
-  node
-    .children()
-    .find(|node| node.has_tag_name("filer"))
-    .ok_or("filer not found".to_string())
-    .and_then(|filer_node| {
-      let credentials = parse_credentials(&filer_node)?;
-      let file_number = parse_string::<String>(&filer_node, "fileNumber");
-
-      Ok(Filer {
-        credentials,
-        file_number,
-      })
+fn parse_filer(node: &Node, diagnostics: &Diagnostics) -> Result<Filer, ParseError> {
+  find_required(node, "filer").and_then(|filer_node| {
+    let credentials = parse_credentials(&filer_node, diagnostics)?;
+    let file_number = parse_string::<String>(&filer_node, "fileNumber");
+
+    Ok(Filer {
+      credentials,
+      file_number,
     })
+  })
 }
 
-fn parse_credentials(node: &Node) -> Result<Credentials, String> {
-  node
-    .children()
-    .find(|node| node.has_tag_name("credentials"))
-    .ok_or("credentials not found".to_string())
-    .and_then(|credentials_node| {
-      let cik =
-        parse_string::<String>(&credentials_node, "cik").ok_or("cik not found".to_string())?;
-      let ccc =
-        parse_string::<String>(&credentials_node, "ccc").ok_or("ccc not found".to_string())?;
-
-      Ok(Credentials { cik, ccc })
-    })
+fn parse_credentials(node: &Node, diagnostics: &Diagnostics) -> Result<Credentials, ParseError> {
+  find_required(node, "credentials").and_then(|credentials_node| {
+    let cik = parse_field::<String>(&credentials_node, "cik", diagnostics)?;
+    let ccc = parse_field::<String>(&credentials_node, "ccc", diagnostics)?;
+
+    Ok(Credentials { cik, ccc })
+  })
 }
 
-fn parse_contact(node: &Node) -> Result<Option<Contact>, String> {
+fn parse_contact(node: &Node, diagnostics: &Diagnostics) -> Result<Option<Contact>, ParseError> {
   node
     .children()
     .find(|node| node.has_tag_name("contact"))
     .map(|contact_node| {
+      diagnostics.unknown_elements(&contact_node, CONTACT_ELEMENTS)?;
       let name = parse_string::<String>(&contact_node, "contactName");
       let phone_number = parse_string::<String>(&contact_node, "contactPhoneNumber");
       let email_address = parse_string::<String>(&contact_node, "contactEmailAddress");
@@ -314,96 +503,103 @@ fn parse_contact(node: &Node) -> Result<Option<Contact>, String> {
     .transpose()
 }
 
-fn parse_notifications(node: &Node) -> Result<Option<Notifications>, String> {
+fn parse_notifications(
+  node: &Node,
+  diagnostics: &Diagnostics,
+) -> Result<Option<Notifications>, ParseError> {
   node
     .children()
     .find(|node| node.has_tag_name("notifications"))
     .map(|notifications_node| {
-      let email_address = parse_string::<String>(&notifications_node, "emailAddress");
+      diagnostics.unknown_elements(&notifications_node, &["notificationEmailAddress"])?;
+      let email_address = notifications_node
+        .children()
+        .filter(|node| node.has_tag_name("notificationEmailAddress"))
+        .filter_map(|node| node.text())
+        .map(|text| text.trim().to_owned())
+        .collect();
 
       Ok(Notifications { email_address })
     })
     .transpose()
 }
 
-fn parse_form_data(node: &Node) -> Result<FormData, String> {
-  node
-    .children()
-    .find(|node| node.has_tag_name("formData"))
-    .ok_or("formData not found".to_string())
-    .and_then(|form_data_node| {
-      let cover_page = parse_cover_page(&form_data_node)?;
-      let signature_block = parse_signature_block(&form_data_node)?;
-      let summary_page = parse_summary_page(&form_data_node)?;
-      let documents = parse_documents(&form_data_node);
-
-      Ok(FormData {
-        cover_page,
-        signature_block,
-        summary_page,
-        documents,
-      })
+fn parse_form_data(node: &Node, diagnostics: &Diagnostics) -> Result<FormData, ParseError> {
+  find_required(node, "formData").and_then(|form_data_node| {
+    diagnostics.unknown_elements(&form_data_node, FORM_DATA_ELEMENTS)?;
+    let cover_page = parse_cover_page(&form_data_node, diagnostics)?;
+    let signature_block = parse_signature_block(&form_data_node, diagnostics)?;
+    let summary_page = parse_summary_page(&form_data_node, diagnostics)?;
+    let documents = parse_documents(&form_data_node, diagnostics)?;
+
+    Ok(FormData {
+      cover_page,
+      signature_block,
+      summary_page,
+      documents,
     })
+  })
 }
 
-fn parse_cover_page(node: &Node) -> Result<CoverPage, String> {
-  node
-    .children()
-    .find(|node| node.has_tag_name("coverPage"))
-    .ok_or("coverPage not found".to_string())
-    .and_then(|cover_page_node| {
-      let report_calendar_or_quarter =
-        parse_string::<String>(&cover_page_node, "reportCalendarOrQuarter")
-          .ok_or("reportCalendarOrQuarter not found".to_string())?;
-      let is_amendment = parse_string::<bool>(&cover_page_node, "isAmendment");
-      let amendment_number = parse_string::<i32>(&cover_page_node, "amendmentNo");
-      let amendment_info = parse_amendment_info(&cover_page_node)?;
-      let filing_manager = parse_filing_manager(&cover_page_node)?;
-      let report_type = parse_string::<String>(&cover_page_node, "reportType")
-        .ok_or("reportType not found".to_string())?;
-      let form_13f_file_number = parse_string::<String>(&cover_page_node, "form13FFileNumber");
-      let crd_number = parse_string::<i32>(&cover_page_node, "crdNumber");
-      let sec_file_number = parse_string::<String>(&cover_page_node, "secFileNumber");
-      let other_managers_info = parse_other_managers_info(&cover_page_node)?;
-      let provide_info_for_instruction_5 =
-        parse_string::<bool>(&cover_page_node, "provideInfoForInstruction5")
-          .ok_or("provideInfoForInstruction5 not found".to_string())?;
-      let additional_information =
-        parse_string::<String>(&cover_page_node, "additionalInformation");
-
-      Ok(CoverPage {
-        report_calendar_or_quarter,
-        is_amendment,
-        amendment_number,
-        amendment_info,
-        filing_manager,
-        report_type,
-        form_13f_file_number,
-        crd_number,
-        sec_file_number,
-        other_managers_info,
-        provide_info_for_instruction_5,
-        additional_information,
-      })
+fn parse_cover_page(node: &Node, diagnostics: &Diagnostics) -> Result<CoverPage, ParseError> {
+  find_required(node, "coverPage").and_then(|cover_page_node| {
+    diagnostics.unknown_elements(&cover_page_node, COVER_PAGE_ELEMENTS)?;
+    let report_calendar_or_quarter =
+      parse_field::<UsDate>(&cover_page_node, "reportCalendarOrQuarter", diagnostics)?;
+    let is_amendment = parse_optional::<bool>(&cover_page_node, "isAmendment", diagnostics)?;
+    let amendment_number = parse_optional::<i32>(&cover_page_node, "amendmentNo", diagnostics)?;
+    let amendment_info = parse_amendment_info(&cover_page_node, diagnostics)?;
+    let filing_manager = parse_filing_manager(&cover_page_node, diagnostics)?;
+    let report_type = parse_recoverable::<ReportType>(&cover_page_node, "reportType", diagnostics)?;
+    let form_13f_file_number = parse_string::<String>(&cover_page_node, "form13FFileNumber");
+    let crd_number = parse_optional::<i32>(&cover_page_node, "crdNumber", diagnostics)?;
+    let sec_file_number = parse_string::<String>(&cover_page_node, "secFileNumber");
+    let other_managers_info = parse_other_managers_info(&cover_page_node, diagnostics)?;
+    let provide_info_for_instruction_5 =
+      parse_field::<bool>(&cover_page_node, "provideInfoForInstruction5", diagnostics)?;
+    let additional_information = parse_string::<String>(&cover_page_node, "additionalInformation");
+
+    Ok(CoverPage {
+      report_calendar_or_quarter,
+      is_amendment,
+      amendment_number,
+      amendment_info,
+      filing_manager,
+      report_type,
+      form_13f_file_number,
+      crd_number,
+      sec_file_number,
+      other_managers_info,
+      provide_info_for_instruction_5,
+      additional_information,
     })
+  })
 }
 
-fn parse_amendment_info(node: &Node) -> Result<Option<AmendmentInfo>, String> {
+fn parse_amendment_info(
+  node: &Node,
+  diagnostics: &Diagnostics,
+) -> Result<Option<AmendmentInfo>, ParseError> {
   node
     .children()
     .find(|node| node.has_tag_name("amendmentInfo"))
     .map(|amendment_info_node| {
-      let amendment_type = parse_string::<String>(&amendment_info_node, "amendmentType");
-      let conf_denied_expired = parse_string::<bool>(&amendment_info_node, "confDeniedExpired");
-      let data_denied_expired = parse_string::<String>(&amendment_info_node, "dataDeniedExpired");
-      let date_reported = parse_string::<String>(&amendment_info_node, "dataReported");
+      diagnostics.unknown_elements(&amendment_info_node, AMENDMENT_INFO_ELEMENTS)?;
+      let amendment_type =
+        parse_optional::<AmendmentType>(&amendment_info_node, "amendmentType", diagnostics)?;
+      let conf_denied_expired =
+        parse_optional::<bool>(&amendment_info_node, "confDeniedExpired", diagnostics)?;
+      let date_denied_expired =
+        parse_optional::<UsDate>(&amendment_info_node, "dateDeniedExpired", diagnostics)?;
+      let date_reported =
+        parse_optional::<UsDate>(&amendment_info_node, "dateReported", diagnostics)?;
       let reason_for_non_confidentiality =
         parse_string::<String>(&amendment_info_node, "reasonForNonConfidentiality");
 
       Ok(AmendmentInfo {
         amendment_type,
         conf_denied_expired,
-        data_denied_expired,
+        date_denied_expired,
         date_reported,
         reason_for_non_confidentiality,
       })
@@ -411,127 +607,139 @@ fn parse_amendment_info(node: &Node) -> Result<Option<AmendmentInfo>, String> {
     .transpose()
 }
 
-fn parse_filing_manager(node: &Node) -> Result<FilingManager, String> {
-  node
-    .children()
-    .find(|node| node.has_tag_name("filingManager"))
-    .ok_or("filingManager not found".to_string())
-    .and_then(|filing_manager_node| {
-      let name =
-        parse_string::<String>(&filing_manager_node, "name").ok_or("name not found".to_string())?;
-      let address = parse_filing_manager_address(&filing_manager_node)?;
-
-      Ok(FilingManager { name, address })
-    })
+fn parse_filing_manager(
+  node: &Node,
+  diagnostics: &Diagnostics,
+) -> Result<FilingManager, ParseError> {
+  find_required(node, "filingManager").and_then(|filing_manager_node| {
+    diagnostics.unknown_elements(&filing_manager_node, &["name", "address"])?;
+    let name = parse_field::<String>(&filing_manager_node, "name", diagnostics)?;
+    let address = parse_filing_manager_address(&filing_manager_node, diagnostics)?;
+
+    Ok(FilingManager { name, address })
+  })
 }
 
-fn parse_filing_manager_address(node: &Node) -> Result<Address, String> {
-  node
-    .children()
-    .find(|node| node.has_tag_name("address"))
-    .ok_or("address not found".to_string())
-    .and_then(|filing_manager_address_node| {
-      let street1 = parse_string::<String>(&filing_manager_address_node, "street1")
-        .ok_or("street1 not found".to_string())?;
-      let street2 = parse_string::<String>(&filing_manager_address_node, "street2");
-      let city = parse_string::<String>(&filing_manager_address_node, "city")
-        .ok_or("city not found".to_string())?;
-      let state_or_country = parse_string::<String>(&filing_manager_address_node, "stateOrCountry")
-        .ok_or("stateOrCountry not found".to_string())?;
-      let zip_code = parse_string::<String>(&filing_manager_address_node, "zipCode")
-        .ok_or("zipCode not found".to_string())?;
-
-      Ok(Address {
-        street1,
-        street2,
-        city,
-        state_or_country,
-        zip_code,
-      })
+fn parse_filing_manager_address(
+  node: &Node,
+  diagnostics: &Diagnostics,
+) -> Result<Address, ParseError> {
+  find_required(node, "address").and_then(|filing_manager_address_node| {
+    diagnostics.unknown_elements(&filing_manager_address_node, ADDRESS_ELEMENTS)?;
+    let street1 = parse_field::<String>(&filing_manager_address_node, "street1", diagnostics)?;
+    let street2 = parse_string::<String>(&filing_manager_address_node, "street2");
+    let city = parse_field::<String>(&filing_manager_address_node, "city", diagnostics)?;
+    let state_or_country =
+      parse_field::<String>(&filing_manager_address_node, "stateOrCountry", diagnostics)?;
+    let zip_code = parse_field::<String>(&filing_manager_address_node, "zipCode", diagnostics)?;
+
+    Ok(Address {
+      street1,
+      street2,
+      city,
+      state_or_country,
+      zip_code,
     })
+  })
 }
 
-fn parse_other_managers_info(node: &Node) -> Result<Option<OtherManagersInfo>, String> {
+fn parse_other_managers_info(
+  node: &Node,
+  diagnostics: &Diagnostics,
+) -> Result<Option<OtherManagersInfo>, ParseError> {
   node
     .children()
     .find(|node| node.has_tag_name("otherManagersInfo"))
     .map(|other_manager_info_node| {
-      let other_manager = parse_other_manager(&other_manager_info_node)?;
+      diagnostics.unknown_elements(&other_manager_info_node, &["otherManager"])?;
+      let other_manager = parse_rows(
+        &other_manager_info_node,
+        "otherManager",
+        diagnostics,
+        |other_manager_node| parse_manager(other_manager_node, diagnostics),
+      )?;
       Ok(OtherManagersInfo { other_manager })
     })
     .transpose()
 }
 
-fn parse_other_manager(node: &Node) -> Result<Option<OtherManager>, String> {
+fn parse_other_manager(
+  node: &Node,
+  diagnostics: &Diagnostics,
+) -> Result<Option<OtherManager>, ParseError> {
   node
     .children()
     .find(|node| node.has_tag_name("otherManager"))
-    .map(|other_manager_node| {
-      let cik = parse_string::<String>(&other_manager_node, "cik");
-      let name = parse_string::<String>(&other_manager_node, "name");
-      let form_13f_file_number = parse_string::<String>(&other_manager_node, "form13FFileNumber");
-      let crd_number = parse_string::<i32>(&other_manager_node, "crdNumber");
-      let sec_file_number = parse_string::<String>(&other_manager_node, "secFileNumber");
-
-      Ok(OtherManager {
-        cik,
-        name,
-        form_13f_file_number,
-        crd_number,
-        sec_file_number,
-      })
-    })
+    .map(|other_manager_node| parse_manager(&other_manager_node, diagnostics))
     .transpose()
 }
 
-fn parse_signature_block(node: &Node) -> Result<SignatureBlock, String> {
-  node
-    .children()
-    .find(|node| node.has_tag_name("signatureBlock"))
-    .ok_or("signatureBlock not found".to_string())
-    .and_then(|signature_block_node| {
-      let name = parse_string::<String>(&signature_block_node, "name")
-        .ok_or("name not found".to_string())?;
-      let title = parse_string::<String>(&signature_block_node, "title")
-        .ok_or("title not found".to_string())?;
-      let phone = parse_string::<String>(&signature_block_node, "phone")
-        .ok_or("phone not found".to_string())?;
-      let signature = parse_string::<String>(&signature_block_node, "signature")
-        .ok_or("signature not found".to_string())?;
-      let city = parse_string::<String>(&signature_block_node, "city")
-        .ok_or("city not found".to_string())?;
-      let state_or_country = parse_string::<String>(&signature_block_node, "stateOrCountry")
-        .ok_or("stateOrCountry not found".to_string())?;
-      let signature_date = parse_string::<String>(&signature_block_node, "signatureDate")
-        .ok_or("signatureDate not found".to_string())?;
-
-      Ok(SignatureBlock {
-        name,
-        title,
-        phone,
-        signature,
-        city,
-        state_or_country,
-        signature_date,
-      })
+fn parse_manager(node: &Node, diagnostics: &Diagnostics) -> Result<OtherManager, ParseError> {
+  diagnostics.unknown_elements(node, OTHER_MANAGER_ELEMENTS)?;
+  let cik = parse_string::<String>(node, "cik");
+  let name = parse_string::<String>(node, "name");
+  let form_13f_file_number = parse_string::<String>(node, "form13FFileNumber");
+  let crd_number = parse_optional::<i32>(node, "crdNumber", diagnostics)?;
+  let sec_file_number = parse_string::<String>(node, "secFileNumber");
+
+  Ok(OtherManager {
+    cik,
+    name,
+    form_13f_file_number,
+    crd_number,
+    sec_file_number,
+  })
+}
+
+fn parse_signature_block(
+  node: &Node,
+  diagnostics: &Diagnostics,
+) -> Result<SignatureBlock, ParseError> {
+  find_required(node, "signatureBlock").and_then(|signature_block_node| {
+    diagnostics.unknown_elements(&signature_block_node, SIGNATURE_BLOCK_ELEMENTS)?;
+    let name = parse_field::<String>(&signature_block_node, "name", diagnostics)?;
+    let title = parse_field::<String>(&signature_block_node, "title", diagnostics)?;
+    let phone = parse_field::<String>(&signature_block_node, "phone", diagnostics)?;
+    let signature = parse_field::<String>(&signature_block_node, "signature", diagnostics)?;
+    let city = parse_field::<String>(&signature_block_node, "city", diagnostics)?;
+    let state_or_country =
+      parse_field::<String>(&signature_block_node, "stateOrCountry", diagnostics)?;
+    let signature_date =
+      parse_field::<UsDate>(&signature_block_node, "signatureDate", diagnostics)?;
+
+    Ok(SignatureBlock {
+      name,
+      title,
+      phone,
+      signature,
+      city,
+      state_or_country,
+      signature_date,
     })
+  })
 }
 
-fn parse_summary_page(node: &Node) -> Result<Option<SummaryPage>, String> {
+fn parse_summary_page(
+  node: &Node,
+  diagnostics: &Diagnostics,
+) -> Result<Option<SummaryPage>, ParseError> {
   node
     .children()
     .find(|node| node.has_tag_name("summaryPage"))
     .map(|summary_page_node| {
-      let other_included_managers_count =
-        parse_string::<i32>(&summary_page_node, "otherIncludedManagersCount")
-          .ok_or("otherIncludedManagersCount not found".to_string())?;
-      let table_entry_total = parse_string::<i32>(&summary_page_node, "tableEntryTotal")
-        .ok_or("tableEntryTotal not found".to_string())?;
-      let table_value_total = parse_string::<i64>(&summary_page_node, "tableValueTotal")
-        .ok_or("tableValueTotal not found".to_string())?;
+      diagnostics.unknown_elements(&summary_page_node, SUMMARY_PAGE_ELEMENTS)?;
+      let other_included_managers_count = parse_field::<i32>(
+        &summary_page_node,
+        "otherIncludedManagersCount",
+        diagnostics,
+      )?;
+      let table_entry_total =
+        parse_field::<i32>(&summary_page_node, "tableEntryTotal", diagnostics)?;
+      let table_value_total =
+        parse_field::<i64>(&summary_page_node, "tableValueTotal", diagnostics)?;
       let is_confidential_omitted =
-        parse_string::<bool>(&summary_page_node, "isConfidentialOmitted");
-      let other_managers = parse_other_managers(&summary_page_node)?;
+        parse_optional::<bool>(&summary_page_node, "isConfidentialOmitted", diagnostics)?;
+      let other_managers = parse_other_managers(&summary_page_node, diagnostics)?;
 
       Ok(SummaryPage {
         other_included_managers_count,
@@ -544,131 +752,156 @@ fn parse_summary_page(node: &Node) -> Result<Option<SummaryPage>, String> {
     .transpose()
 }
 
-fn parse_other_managers(node: &Node) -> Result<Vec<OtherManagerWithSequence>, String> {
-  let managers = node
+fn parse_other_managers(
+  node: &Node,
+  diagnostics: &Diagnostics,
+) -> Result<Vec<OtherManagerWithSequence>, ParseError> {
+  let mut managers = vec![];
+  for info_node in node
     .children()
     .filter(|node| node.has_tag_name("otherManagers2Info"))
-    .flat_map(|node| node.children())
-    .filter(|node| node.has_tag_name("otherManager2"))
-    .filter_map(|manager_node| {
-      let sequence_number = parse_string::<i32>(&manager_node, "sequenceNumber");
-      let manager = parse_other_manager(&manager_node).ok()?;
-
-      Some(OtherManagerWithSequence {
-        sequence_number,
-        manager,
-      })
-    })
-    .collect();
+  {
+    diagnostics.unknown_elements(&info_node, &["otherManager2"])?;
+    managers.extend(parse_rows(
+      &info_node,
+      "otherManager2",
+      diagnostics,
+      |manager_node| {
+        diagnostics.unknown_elements(manager_node, &["sequenceNumber", "otherManager"])?;
+        let sequence_number = parse_optional::<i32>(manager_node, "sequenceNumber", diagnostics)?;
+        let manager = parse_other_manager(manager_node, diagnostics)?;
+
+        Ok(OtherManagerWithSequence {
+          sequence_number,
+          manager,
+        })
+      },
+    )?);
+  }
 
   Ok(managers)
 }
 
-fn parse_documents(node: &Node) -> Option<Vec<OtherDocument>> {
-  let documents: Vec<OtherDocument> = node
+fn parse_documents(
+  node: &Node,
+  diagnostics: &Diagnostics,
+) -> Result<Option<Vec<OtherDocument>>, ParseError> {
+  let mut documents = vec![];
+  for documents_node in node
     .children()
     .filter(|node| node.has_tag_name("documents"))
-    .flat_map(|node| node.children())
-    .filter(|node| node.has_tag_name("document"))
-    .filter_map(|manager_node| {
-      let conformed_name = parse_string::<String>(&manager_node, "conformedName");
-      let conformed_document_type = parse_string::<String>(&manager_node, "conformedDocumentType");
-      let description = parse_string::<String>(&manager_node, "description");
-      let contents = parse_string::<String>(&manager_node, "contents");
-
-      Some(OtherDocument {
+  {
+    diagnostics.unknown_elements(&documents_node, &["document"])?;
+    for document_node in documents_node
+      .children()
+      .filter(|node| node.has_tag_name("document"))
+    {
+      diagnostics.unknown_elements(&document_node, DOCUMENT_ELEMENTS)?;
+      let conformed_name = parse_string::<String>(&document_node, "conformedName");
+      let conformed_document_type = parse_string::<String>(&document_node, "conformedDocumentType");
+      let description = parse_string::<String>(&document_node, "description");
+      let contents = parse_string::<String>(&document_node, "contents");
+
+      documents.push(OtherDocument {
         conformed_name,
         conformed_document_type,
         description,
         contents,
-      })
-    })
-    .collect();
+      });
+    }
+  }
 
-  Some(documents)
+  Ok(Some(documents))
 }
 
 #[napi]
-pub fn parse_form13f_table(table: String) -> Result<Form13FTable, Error> {
-  let doc = XMLDoc::parse(&table)
-    .map_err(|err| Error::from_reason(format!("Failed to parse Form 13F table: {}", err)))?;
+pub fn parse_form13f_table(
+  env: Env,
+  table: String,
+  options: Option<ParseOptions>,
+) -> Result<Form13FTable, Error> {
+  let doc = XMLDoc::parse(&table).map_err(|e| ParseError::from(e).into_napi(env))?;
   let root_node = doc.root_element();
 
-  let entries = root_node
-    .children()
-    .filter(|root_node| root_node.has_tag_name("infoTable"))
-    .filter_map(|info_node| {
-      let name_of_issuer = parse_string::<String>(&info_node, "nameOfIssuer")
-        .ok_or("nameOfIssuer not found".to_string())
-        .ok()?;
-      let title_of_class = parse_string::<String>(&info_node, "titleOfClass")
-        .ok_or("titleOfClass not found".to_string())
-        .ok()?;
-      let cusip = parse_string::<String>(&info_node, "cusip")
-        .ok_or("cusip not found".to_string())
-        .ok()?;
-      let figi = parse_string::<String>(&info_node, "figi");
-      let value = parse_string::<i64>(&info_node, "value")
-        .ok_or("value not found".to_string())
-        .ok()?;
-      let shares_or_print_amount = parse_shares_or_print_amount(&info_node).ok()?;
-      let put_call = parse_string::<String>(&info_node, "putCall");
-      let investment_discretion = parse_string::<String>(&info_node, "investmentDiscretion")
-        .ok_or("investmentDiscretion not found".to_string())
-        .ok()?;
-      let other_manager = parse_ints(&info_node, "otherManager");
-      let voting_authority = parse_voting_authority(&info_node).ok()?;
-
-      Some(TableEntry {
-        name_of_issuer,
-        title_of_class,
-        cusip,
-        figi,
-        value,
-        shares_or_print_amount,
-        put_call,
-        investment_discretion,
-        other_manager: Some(other_manager),
-        voting_authority,
-      })
-    })
-    .collect();
+  let diagnostics = &Diagnostics::new(ParseOptions::mode(&options));
+  diagnostics
+    .unknown_elements(&root_node, &["infoTable"])
+    .map_err(|e| e.into_napi(env))?;
+  let entries = parse_rows(&root_node, "infoTable", diagnostics, |info_node| {
+    diagnostics.unknown_elements(info_node, INFO_TABLE_ELEMENTS)?;
+    parse_table_entry(info_node, diagnostics)
+  })
+  .map_err(|e| e.into_napi(env))?;
 
-  Ok(Form13FTable { entries })
+  Ok(Form13FTable {
+    entries,
+    warnings: diagnostics.take_warnings(),
+  })
 }
 
-fn parse_shares_or_print_amount(node: &Node) -> Result<SharesOrPrintAmount, String> {
-  node
-    .children()
-    .find(|node| node.has_tag_name("shrsOrPrnAmt"))
-    .ok_or("shrsOrPrnAmt not found".to_string())
-    .and_then(|shares_or_principal_amount_node| {
-      let amount = parse_string::<i64>(&shares_or_principal_amount_node, "sshPrnamt")
-        .ok_or("sshPrnamt not found".to_string())?;
-      let shares_or_print_type =
-        parse_string::<String>(&shares_or_principal_amount_node, "sshPrnamtType")
-          .ok_or("sshPrnamtType not found".to_string())?;
-
-      Ok(SharesOrPrintAmount {
-        amount,
-        shares_or_print_type,
-      })
-    })
+fn parse_table_entry(
+  info_node: &Node,
+  diagnostics: &Diagnostics,
+) -> Result<TableEntry, ParseError> {
+  let name_of_issuer = parse_required::<String>(info_node, "nameOfIssuer")?;
+  let title_of_class = parse_required::<String>(info_node, "titleOfClass")?;
+  let cusip = parse_required::<String>(info_node, "cusip")?;
+  let figi = parse_string::<String>(info_node, "figi");
+  let value = parse_required::<i64>(info_node, "value")?;
+  let shares_or_print_amount = parse_shares_or_print_amount(info_node, diagnostics)?;
+  let put_call = parse_optional::<PutCall>(info_node, "putCall", diagnostics)?;
+  let investment_discretion =
+    parse_recoverable::<InvestmentDiscretion>(info_node, "investmentDiscretion", diagnostics)?;
+  let other_manager = parse_ints(info_node, "otherManager", diagnostics)?;
+  let voting_authority = parse_voting_authority(info_node, diagnostics)?;
+
+  Ok(TableEntry {
+    name_of_issuer,
+    title_of_class,
+    cusip,
+    figi,
+    value,
+    shares_or_print_amount,
+    put_call,
+    investment_discretion,
+    other_manager: Some(other_manager),
+    voting_authority,
+  })
 }
 
-fn parse_voting_authority(node: &Node) -> Result<VotingAuthority, String> {
-  node
-    .children()
-    .find(|node| node.has_tag_name("votingAuthority"))
-    .ok_or("votingAuthority not found".to_string())
-    .and_then(|voting_authority_node| {
-      let sole =
-        parse_string::<i32>(&voting_authority_node, "Sole").ok_or("Sole not found".to_string())?;
-      let shared = parse_string::<i32>(&voting_authority_node, "Shared")
-        .ok_or("Shared not found".to_string())?;
-      let none =
-        parse_string::<i32>(&voting_authority_node, "None").ok_or("None not found".to_string())?;
-
-      Ok(VotingAuthority { sole, shared, none })
+fn parse_shares_or_print_amount(
+  node: &Node,
+  diagnostics: &Diagnostics,
+) -> Result<SharesOrPrintAmount, ParseError> {
+  find_required(node, "shrsOrPrnAmt").and_then(|shares_or_principal_amount_node| {
+    diagnostics.unknown_elements(
+      &shares_or_principal_amount_node,
+      &["sshPrnamt", "sshPrnamtType"],
+    )?;
+    let amount = parse_required::<i64>(&shares_or_principal_amount_node, "sshPrnamt")?;
+    let shares_or_print_type = parse_recoverable::<SharesOrPrintType>(
+      &shares_or_principal_amount_node,
+      "sshPrnamtType",
+      diagnostics,
+    )?;
+
+    Ok(SharesOrPrintAmount {
+      amount,
+      shares_or_print_type,
     })
+  })
+}
+
+fn parse_voting_authority(
+  node: &Node,
+  diagnostics: &Diagnostics,
+) -> Result<VotingAuthority, ParseError> {
+  find_required(node, "votingAuthority").and_then(|voting_authority_node| {
+    diagnostics.unknown_elements(&voting_authority_node, &["Sole", "Shared", "None"])?;
+    let sole = parse_required::<i64>(&voting_authority_node, "Sole")?;
+    let shared = parse_required::<i64>(&voting_authority_node, "Shared")?;
+    let none = parse_required::<i64>(&voting_authority_node, "None")?;
+
+    Ok(VotingAuthority { sole, shared, none })
+  })
 }
@@ -0,0 +1,120 @@
+use napi_derive::napi;
+use std::collections::HashMap;
+
+use crate::form_13f::{
+  Form13FTable, PutCall, SharesOrPrintAmount, SharesOrPrintType, VotingAuthority,
+};
+
+#[napi(object)]
+pub struct AggregateOptions {
+  /// Keep puts, calls and the underlying security as separate holdings. Defaults to true, as an
+  /// option row reports the value of its underlying shares rather than of the option.
+  pub by_put_call: Option<bool>,
+}
+
+impl AggregateOptions {
+  fn by_put_call(options: &Option<AggregateOptions>) -> bool {
+    options
+      .as_ref()
+      .and_then(|options| options.by_put_call)
+      .unwrap_or(true)
+  }
+}
+
+#[napi(object)]
+pub struct AggregatedHolding {
+  pub cusip: String,
+  pub name_of_issuer: String,
+  pub title_of_class: String,
+  /// Only set when aggregating by put/call.
+  #[napi(ts_type = "'Put' | 'Call'")]
+  pub put_call: Option<PutCall>,
+  pub value: i64,
+  pub shares_or_print_amount: SharesOrPrintAmount,
+  pub voting_authority: VotingAuthority,
+  /// Number of information table rows combined into the holding.
+  pub entry_count: u32,
+  /// Set when a sum exceeds the 64-bit integer range; the amounts then stop at the largest
+  /// representable value.
+  pub overflow: bool,
+  /// Share of the table's total value, between 0 and 1.
+  pub weight: f64,
+}
+
+/// Combines the information table rows of each security, which filers split by investment
+/// discretion and other manager, into one holding in order of first appearance. Share and
+/// principal amounts of the same security are kept as separate holdings.
+#[napi]
+pub fn aggregate_form13f_table(
+  table: Form13FTable,
+  options: Option<AggregateOptions>,
+) -> Vec<AggregatedHolding> {
+  let by_put_call = AggregateOptions::by_put_call(&options);
+  let mut holdings: Vec<AggregatedHolding> = vec![];
+  let mut positions: HashMap<(String, Option<SharesOrPrintType>, Option<PutCall>), usize> =
+    HashMap::new();
+  for entry in table.entries {
+    let cusip = entry.cusip.trim().to_uppercase();
+    let put_call = entry.put_call.filter(|_| by_put_call);
+    let key = (
+      cusip.clone(),
+      entry.shares_or_print_amount.shares_or_print_type,
+      put_call,
+    );
+
+    match positions.get(&key) {
+      Some(&index) => {
+        let holding = &mut holdings[index];
+        let mut overflow = false;
+        let mut add = |total: &mut i64, amount: i64| {
+          overflow |= total.checked_add(amount).is_none();
+          *total = total.saturating_add(amount);
+        };
+        add(&mut holding.value, entry.value);
+        add(
+          &mut holding.shares_or_print_amount.amount,
+          entry.shares_or_print_amount.amount,
+        );
+        add(
+          &mut holding.voting_authority.sole,
+          entry.voting_authority.sole,
+        );
+        add(
+          &mut holding.voting_authority.shared,
+          entry.voting_authority.shared,
+        );
+        add(
+          &mut holding.voting_authority.none,
+          entry.voting_authority.none,
+        );
+        holding.entry_count += 1;
+        holding.overflow |= overflow;
+      }
+      None => {
+        positions.insert(key, holdings.len());
+        holdings.push(AggregatedHolding {
+          cusip,
+          name_of_issuer: entry.name_of_issuer,
+          title_of_class: entry.title_of_class,
+          put_call,
+          value: entry.value,
+          shares_or_print_amount: entry.shares_or_print_amount,
+          voting_authority: entry.voting_authority,
+          entry_count: 1,
+          overflow: false,
+          weight: 0.0,
+        });
+      }
+    }
+  }
+
+  // Summed as floats, as the total of all holdings may not fit in an i64.
+  let total_value: f64 = holdings.iter().map(|holding| holding.value as f64).sum();
+  if total_value != 0.0 {
+    for holding in &mut holdings {
+      holding.weight = holding.value as f64 / total_value;
+    }
+  }
+
+  holdings
+}
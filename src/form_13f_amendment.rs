@@ -0,0 +1,88 @@
+use napi::{Error, Status};
+use napi_derive::napi;
+
+use crate::form_13f::{AmendmentType, Form13F, Form13FTable, TableEntry};
+
+#[napi(object)]
+pub struct EffectiveHoldings {
+  #[napi(ts_type = "'RESTATEMENT' | 'NEW HOLDINGS'")]
+  pub amendment_type: AmendmentType,
+  pub amendment_number: Option<i32>,
+  /// The holdings for the quarter after applying the amendment.
+  pub entries: Vec<TableEntry>,
+  /// Number of leading entries taken from the original table.
+  pub original_entry_count: u32,
+}
+
+/// Combines a 13F-HR/A with the filing it amends. A restatement replaces the original
+/// information table, while a new holdings amendment only lists holdings the original omitted.
+/// Amendments are applied one at a time, passing the previous result as the original table.
+#[napi]
+pub fn apply_form13f_amendment(
+  original: Form13F,
+  original_table: Form13FTable,
+  amended: Form13F,
+  amended_table: Form13FTable,
+) -> Result<EffectiveHoldings, Error> {
+  let cover_page = &amended.form_data.cover_page;
+  let submission_type = amended.header_data.submission_type;
+  if !submission_type.is_some_and(|submission_type| submission_type.is_amendment())
+    && cover_page.is_amendment != Some(true)
+  {
+    return Err(Error::new(
+      Status::InvalidArg,
+      format!(
+        "submission {} is not an amendment",
+        submission_type.map_or("of unknown type", |submission_type| submission_type
+          .as_str())
+      ),
+    ));
+  }
+
+  let original_cik = &original.header_data.filer_info.filer.credentials.cik;
+  let amendment_cik = &amended.header_data.filer_info.filer.credentials.cik;
+  if original_cik != amendment_cik {
+    return Err(Error::new(
+      Status::InvalidArg,
+      format!("amendment filer {amendment_cik} does not match original filer {original_cik}"),
+    ));
+  }
+  let original_period = &original.form_data.cover_page.report_calendar_or_quarter;
+  let amendment_period = &cover_page.report_calendar_or_quarter;
+  if original_period != amendment_period {
+    return Err(Error::new(
+      Status::InvalidArg,
+      format!(
+        "amendment period {amendment_period} does not match original period {original_period}"
+      ),
+    ));
+  }
+
+  let amendment_type = cover_page
+    .amendment_info
+    .as_ref()
+    .and_then(|info| info.amendment_type)
+    .ok_or_else(|| {
+      Error::new(
+        Status::InvalidArg,
+        "amendment does not state its amendment type".to_owned(),
+      )
+    })?;
+
+  let (entries, original_entry_count) = match amendment_type {
+    AmendmentType::Restatement => (amended_table.entries, 0),
+    AmendmentType::NewHoldings => {
+      let original_entry_count = original_table.entries.len();
+      let mut entries = original_table.entries;
+      entries.extend(amended_table.entries);
+      (entries, original_entry_count)
+    }
+  };
+
+  Ok(EffectiveHoldings {
+    amendment_type,
+    amendment_number: cover_page.amendment_number,
+    entries,
+    original_entry_count: original_entry_count as u32,
+  })
+}
@@ -0,0 +1,171 @@
+use napi_derive::napi;
+use std::collections::HashMap;
+
+use crate::form_13f::{Form13FTable, PutCall, TableEntry};
+
+#[napi(object)]
+pub struct HoldingChange {
+  pub kind: HoldingChangeKind,
+  pub cusip: String,
+  pub title_of_class: String,
+  #[napi(ts_type = "'Put' | 'Call'")]
+  pub put_call: Option<PutCall>,
+  pub name_of_issuer: String,
+  pub previous_shares: i64,
+  pub current_shares: i64,
+  pub share_change: i64,
+  /// Percent change in shares, absent for new positions.
+  pub share_change_percent: Option<f64>,
+  pub previous_value: i64,
+  pub current_value: i64,
+  pub value_change: i64,
+  /// Percent change in value, absent when the previous value is zero.
+  pub value_change_percent: Option<f64>,
+  /// Set when a sum or change exceeds the 64-bit integer range; the amounts then stop at the
+  /// nearest representable value.
+  pub overflow: bool,
+}
+
+#[napi(string_enum)]
+pub enum HoldingChangeKind {
+  New,
+  Closed,
+  Increased,
+  Decreased,
+  Unchanged,
+}
+
+/// Compares the holdings of two quarters, keyed by CUSIP, title of class and put/call. Entries
+/// sharing a key within a table, such as rows split by other manager, are summed first. Values
+/// are compared as reported, so tables on either side of the 2023 unit change should be
+/// normalized with `normalizeForm13FValues` first.
+#[napi]
+pub fn diff_form13f_tables(previous: Form13FTable, current: Form13FTable) -> Vec<HoldingChange> {
+  let previous_holdings = holdings(&previous.entries);
+  let mut unmatched: HashMap<&HoldingKey, &Holding> = previous_holdings
+    .iter()
+    .map(|holding| (&holding.key, holding))
+    .collect();
+
+  let mut changes = vec![];
+  for holding in &holdings(&current.entries) {
+    let change = match unmatched.remove(&holding.key) {
+      Some(previous) => holding_change(previous, holding),
+      None => holding_change(&Holding::empty(holding), holding),
+    };
+    changes.push(change);
+  }
+  for holding in &previous_holdings {
+    if unmatched.contains_key(&holding.key) {
+      changes.push(holding_change(holding, &Holding::empty(holding)));
+    }
+  }
+
+  changes
+}
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct HoldingKey {
+  cusip: String,
+  title_of_class: String,
+  put_call: Option<PutCall>,
+}
+
+struct Holding {
+  key: HoldingKey,
+  name_of_issuer: String,
+  shares: i64,
+  value: i64,
+  overflow: bool,
+}
+
+impl Holding {
+  fn empty(other: &Holding) -> Holding {
+    Holding {
+      key: other.key.clone(),
+      name_of_issuer: other.name_of_issuer.clone(),
+      shares: 0,
+      value: 0,
+      overflow: false,
+    }
+  }
+}
+
+/// Sums entries by key, in order of first appearance.
+fn holdings(entries: &[TableEntry]) -> Vec<Holding> {
+  let mut holdings: Vec<Holding> = vec![];
+  let mut positions: HashMap<HoldingKey, usize> = HashMap::new();
+  for entry in entries {
+    let key = HoldingKey {
+      cusip: entry.cusip.trim().to_uppercase(),
+      title_of_class: entry.title_of_class.trim().to_uppercase(),
+      put_call: entry.put_call,
+    };
+    match positions.get(&key) {
+      Some(&index) => {
+        let holding = &mut holdings[index];
+        let shares = holding
+          .shares
+          .checked_add(entry.shares_or_print_amount.amount);
+        let value = holding.value.checked_add(entry.value);
+        holding.overflow |= shares.is_none() || value.is_none();
+        holding.shares = holding
+          .shares
+          .saturating_add(entry.shares_or_print_amount.amount);
+        holding.value = holding.value.saturating_add(entry.value);
+      }
+      None => {
+        positions.insert(key.clone(), holdings.len());
+        holdings.push(Holding {
+          key,
+          name_of_issuer: entry.name_of_issuer.trim().to_owned(),
+          shares: entry.shares_or_print_amount.amount,
+          value: entry.value,
+          overflow: false,
+        });
+      }
+    }
+  }
+
+  holdings
+}
+
+fn holding_change(previous: &Holding, current: &Holding) -> HoldingChange {
+  let kind = if previous.shares == 0 && current.shares != 0 {
+    HoldingChangeKind::New
+  } else if current.shares == 0 && previous.shares != 0 {
+    HoldingChangeKind::Closed
+  } else if current.shares > previous.shares {
+    HoldingChangeKind::Increased
+  } else if current.shares < previous.shares {
+    HoldingChangeKind::Decreased
+  } else {
+    HoldingChangeKind::Unchanged
+  };
+
+  let share_change = current.shares.checked_sub(previous.shares);
+  let value_change = current.value.checked_sub(previous.value);
+  let overflow =
+    previous.overflow || current.overflow || share_change.is_none() || value_change.is_none();
+
+  HoldingChange {
+    kind,
+    cusip: current.key.cusip.clone(),
+    title_of_class: current.key.title_of_class.clone(),
+    put_call: current.key.put_call,
+    name_of_issuer: current.name_of_issuer.clone(),
+    previous_shares: previous.shares,
+    current_shares: current.shares,
+    share_change: current.shares.saturating_sub(previous.shares),
+    share_change_percent: percent_change(previous.shares, current.shares),
+    previous_value: previous.value,
+    current_value: current.value,
+    value_change: current.value.saturating_sub(previous.value),
+    value_change_percent: percent_change(previous.value, current.value),
+    overflow,
+  }
+}
+
+fn percent_change(previous: i64, current: i64) -> Option<f64> {
+  (previous != 0).then(|| (current as f64 - previous as f64) / previous as f64 * 100.0)
+}
@@ -0,0 +1,116 @@
+use napi_derive::napi;
+
+use crate::form_13f::{Form13F, Form13FTable};
+
+#[napi(object)]
+pub struct Form13FDiscrepancy {
+  pub kind: DiscrepancyKind,
+  pub message: String,
+  /// Index of the information table entry, for entry-level discrepancies.
+  pub entry_index: Option<u32>,
+}
+
+#[napi(string_enum)]
+pub enum DiscrepancyKind {
+  /// The form has no summary page to check the table against.
+  MissingSummaryPage,
+  /// `tableEntryTotal` differs from the number of table entries.
+  EntryTotalMismatch,
+  /// `tableValueTotal` differs from the sum of the table values, or the sum overflows.
+  ValueTotalMismatch,
+  /// `otherIncludedManagersCount` differs from the number of listed other managers.
+  OtherManagersCountMismatch,
+  /// An entry references an other manager sequence number the summary page does not list.
+  UnknownOtherManager,
+}
+
+/// Checks the totals and other manager list reported on the summary page of a Form 13F against
+/// its information table. Totals are compared in the units the filing reports them in.
+#[napi]
+pub fn validate_form13f_table(form: Form13F, table: Form13FTable) -> Vec<Form13FDiscrepancy> {
+  let Some(summary_page) = form.form_data.summary_page else {
+    return vec![Form13FDiscrepancy {
+      kind: DiscrepancyKind::MissingSummaryPage,
+      message: format!(
+        "{} has no summary page for a table of {} entries",
+        form
+          .header_data
+          .submission_type
+          .map_or("submission", |submission_type| submission_type.as_str()),
+        table.entries.len()
+      ),
+      entry_index: None,
+    }];
+  };
+
+  let mut discrepancies = vec![];
+  let entry_count = table.entries.len() as i64;
+  if summary_page.table_entry_total as i64 != entry_count {
+    discrepancies.push(Form13FDiscrepancy {
+      kind: DiscrepancyKind::EntryTotalMismatch,
+      message: format!(
+        "summary page reports {} entries, information table has {}",
+        summary_page.table_entry_total, entry_count
+      ),
+      entry_index: None,
+    });
+  }
+
+  // A sum beyond the 64-bit range cannot match any reported total.
+  let value_total = table
+    .entries
+    .iter()
+    .try_fold(0i64, |total, entry| total.checked_add(entry.value));
+  let value_total_message = match value_total {
+    Some(value_total) if value_total == summary_page.table_value_total => None,
+    Some(value_total) => Some(format!(
+      "summary page reports a total value of {}, information table values sum to {}",
+      summary_page.table_value_total, value_total
+    )),
+    None => Some(format!(
+      "summary page reports a total value of {}, information table values overflow a 64-bit sum",
+      summary_page.table_value_total
+    )),
+  };
+  if let Some(message) = value_total_message {
+    discrepancies.push(Form13FDiscrepancy {
+      kind: DiscrepancyKind::ValueTotalMismatch,
+      message,
+      entry_index: None,
+    });
+  }
+
+  let other_managers_count = summary_page.other_managers.len() as i64;
+  if summary_page.other_included_managers_count as i64 != other_managers_count {
+    discrepancies.push(Form13FDiscrepancy {
+      kind: DiscrepancyKind::OtherManagersCountMismatch,
+      message: format!(
+        "summary page reports {} other included managers, lists {}",
+        summary_page.other_included_managers_count, other_managers_count
+      ),
+      entry_index: None,
+    });
+  }
+
+  let sequence_numbers: Vec<i32> = summary_page
+    .other_managers
+    .iter()
+    .filter_map(|manager| manager.sequence_number)
+    .collect();
+  for (index, entry) in table.entries.iter().enumerate() {
+    for sequence_number in entry.other_manager.iter().flatten() {
+      if !sequence_numbers.contains(sequence_number) {
+        discrepancies.push(Form13FDiscrepancy {
+          kind: DiscrepancyKind::UnknownOtherManager,
+          message: format!(
+            "entry {} references other manager {} which is not on the summary page",
+            index, sequence_number
+          ),
+          entry_index: Some(index as u32),
+        });
+      }
+    }
+  }
+
+  discrepancies
+}
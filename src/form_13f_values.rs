@@ -0,0 +1,127 @@
+use napi::{Error, Status};
+use napi_derive::napi;
+use std::ops::RangeInclusive;
+
+use crate::form_13f::{Form13F, Form13FTable, SharesOrPrintType, TableEntry};
+
+/// Reports for periods ending on or after this date were filed from January 2023 on, when the
+/// information table switched from thousands of dollars to whole dollars.
+const DOLLAR_VALUES_FROM: (i32, u32, u32) = (2022, 12, 31);
+
+/// Implied prices per share outside this range are implausible. It is wide enough to hold
+/// listed securities trading below $1 and Berkshire Hathaway class A.
+const PLAUSIBLE_PRICES: RangeInclusive<f64> = 0.1..=1_000_000.0;
+
+/// An implausible price is only blamed on the units when correcting it by 1000x lands it here,
+/// where most 13(f) securities trade.
+const TYPICAL_PRICES: RangeInclusive<f64> = 1.0..=10_000.0;
+
+#[napi(object)]
+pub struct NormalizedForm13FTable {
+  pub period_of_report: String,
+  /// Whether values were converted from thousands of dollars.
+  pub reported_in_thousands: bool,
+  /// Entries with `value` in whole dollars.
+  pub entries: Vec<TableEntry>,
+  pub value_flags: Vec<ValueFlag>,
+}
+
+/// An entry whose value is implausible for its share count or could not be converted.
+#[napi(object)]
+pub struct ValueFlag {
+  /// Index of the entry in the table.
+  pub index: u32,
+  /// Absent for entries reported as a principal amount.
+  pub implied_price: Option<f64>,
+  pub kind: ValueFlagKind,
+}
+
+#[napi(string_enum)]
+pub enum ValueFlagKind {
+  /// The value looks 1000x too small, as if reported in thousands of dollars.
+  ThousandTimesTooLow,
+  /// The value looks 1000x too large, as if reported in dollars before 2023.
+  ThousandTimesTooHigh,
+  /// The value in thousands does not fit in whole dollars and is left as reported.
+  Overflow,
+}
+
+/// Converts information table values to whole dollars using the period of report of the
+/// companion Form 13F, and flags share entries whose implied price per share is implausible but
+/// becomes typical when corrected by 1000x.
+#[napi]
+pub fn normalize_form13f_values(
+  table: Form13FTable,
+  form: Form13F,
+) -> Result<NormalizedForm13FTable, Error> {
+  let period_of_report = form.header_data.filer_info.period_of_report;
+  let period = parse_period(&period_of_report).ok_or_else(|| {
+    Error::new(
+      Status::InvalidArg,
+      format!("invalid period of report: {period_of_report}"),
+    )
+  })?;
+  let reported_in_thousands = period < DOLLAR_VALUES_FROM;
+
+  let mut entries = table.entries;
+  let mut value_flags = vec![];
+  for (index, entry) in entries.iter_mut().enumerate() {
+    let shares = &entry.shares_or_print_amount;
+    let share_count = (shares.shares_or_print_type == Some(SharesOrPrintType::Shares)
+      && shares.amount > 0)
+      .then_some(shares.amount as f64);
+
+    if reported_in_thousands {
+      match entry.value.checked_mul(1000) {
+        Some(value) => entry.value = value,
+        None => {
+          value_flags.push(ValueFlag {
+            index: index as u32,
+            implied_price: share_count.map(|count| entry.value as f64 * 1000.0 / count),
+            kind: ValueFlagKind::Overflow,
+          });
+          continue;
+        }
+      }
+    }
+
+    let Some(share_count) = share_count else {
+      continue;
+    };
+    let implied_price = entry.value as f64 / share_count;
+    let kind = if PLAUSIBLE_PRICES.contains(&implied_price) {
+      continue;
+    } else if TYPICAL_PRICES.contains(&(implied_price * 1000.0)) {
+      ValueFlagKind::ThousandTimesTooLow
+    } else if TYPICAL_PRICES.contains(&(implied_price / 1000.0)) {
+      ValueFlagKind::ThousandTimesTooHigh
+    } else {
+      continue;
+    };
+    value_flags.push(ValueFlag {
+      index: index as u32,
+      implied_price: Some(implied_price),
+      kind,
+    });
+  }
+
+  Ok(NormalizedForm13FTable {
+    period_of_report,
+    reported_in_thousands,
+    entries,
+    value_flags,
+  })
+}
+
+/// Parses a `YYYY-MM-DD` period of report.
+fn parse_period(period: &str) -> Option<(i32, u32, u32)> {
+  let mut parts = period.trim().split('-');
+  let year = parts.next()?.parse().ok()?;
+  let month = parts.next()?.parse().ok()?;
+  let day = parts.next()?.parse().ok()?;
+  if parts.next().is_some() {
+    return None;
+  }
+
+  Some((year, month, day))
+}
@@ -0,0 +1,381 @@
+use napi::{Env, Error};
+use napi_derive::napi;
+use roxmltree::{Document as XMLDoc, Node};
+
+use crate::error::ParseError;
+use crate::form_13f::LiveTestFlag;
+use crate::{
+  find_required, parse_field, parse_optional, parse_recoverable, parse_required, parse_rows,
+  parse_string, Diagnostic, Diagnostics, ParseFromString, ParseOptions, UsDate,
+};
+
+#[napi(object)]
+pub struct Form144 {
+  /// Unset when lenient parsing meets an invalid submission type.
+  #[napi(ts_type = "'144' | '144/A'")]
+  pub submission_type: Option<Form144SubmissionType>,
+  pub filer_cik: Option<String>,
+  #[napi(ts_type = "'LIVE' | 'TEST'")]
+  pub live_test_flag: Option<LiveTestFlag>,
+  pub issuer: Form144Issuer,
+  /// The person for whose account the securities are to be sold.
+  pub seller: Form144Seller,
+  pub securities_to_be_sold: Vec<SecuritiesToBeSold>,
+  pub securities_acquired: Vec<SecuritiesAcquired>,
+  pub nothing_to_report_past_three_months: Option<bool>,
+  pub sales_past_three_months: Vec<PastSale>,
+  pub remarks: Option<String>,
+  pub notice_date: Option<String>,
+  pub plan_adoption_dates: Vec<String>,
+  pub signature: Option<String>,
+  pub warnings: Vec<Diagnostic>,
+}
+
+#[napi(object)]
+pub struct Form144Issuer {
+  pub cik: String,
+  pub name: String,
+  pub sec_file_number: Option<String>,
+  pub address: Option<Form144Address>,
+  pub contact_phone: Option<String>,
+}
+
+#[napi(object)]
+pub struct Form144Seller {
+  pub name: String,
+  pub relationships_to_issuer: Vec<String>,
+}
+
+#[napi(object)]
+pub struct Form144Address {
+  pub street1: Option<String>,
+  pub street2: Option<String>,
+  pub city: Option<String>,
+  pub state_or_country: Option<String>,
+  pub zip_code: Option<String>,
+}
+
+#[napi(object)]
+pub struct Broker {
+  pub name: String,
+  pub address: Option<Form144Address>,
+}
+
+#[napi(object)]
+pub struct SecuritiesToBeSold {
+  pub class_title: String,
+  pub broker: Option<Broker>,
+  pub units_to_be_sold: f64,
+  pub aggregate_market_value: f64,
+  pub units_outstanding: Option<f64>,
+  pub approximate_sale_date: Option<String>,
+  pub exchange: Option<String>,
+}
+
+#[napi(object)]
+pub struct SecuritiesAcquired {
+  pub class_title: String,
+  pub acquired_date: Option<String>,
+  pub nature_of_acquisition: Option<String>,
+  pub acquired_from: Option<String>,
+  pub is_gift: Option<bool>,
+  pub amount_acquired: f64,
+  pub payment_date: Option<String>,
+  pub nature_of_payment: Option<String>,
+}
+
+#[napi(object)]
+pub struct PastSale {
+  pub seller_name: Option<String>,
+  pub seller_address: Option<Form144Address>,
+  pub class_title: String,
+  pub sale_date: Option<String>,
+  pub amount_sold: f64,
+  pub gross_proceeds: Option<f64>,
+}
+
+string_union! {
+  pub enum Form144SubmissionType {
+    Notice = "144",
+    NoticeAmendment = "144/A",
+  }
+}
+
+const FORM_DATA_ELEMENTS: &[&str] = &[
+  "issuerInfo",
+  "securitiesInformation",
+  "securitiesToBeSold",
+  "nothingToReportFlagOnSecuritiesSoldInPast3Months",
+  "securitiesSoldInPast3Months",
+  "remarks",
+  "noticeSignature",
+];
+
+const ISSUER_INFO_ELEMENTS: &[&str] = &[
+  "issuerCik",
+  "issuerName",
+  "secFileNumber",
+  "issuerAddress",
+  "issuerContactPhone",
+  "nameOfPersonForWhoseAccountTheSecuritiesAreToBeSold",
+  "relationshipsToIssuer",
+];
+
+const SECURITIES_INFORMATION_ELEMENTS: &[&str] = &[
+  "securitiesClassTitle",
+  "brokerOrMarketmakerDetails",
+  "noOfUnitsSold",
+  "aggregateMarketValue",
+  "noOfUnitsOutstanding",
+  "approxSaleDate",
+  "securitiesExchangeName",
+];
+
+const SECURITIES_TO_BE_SOLD_ELEMENTS: &[&str] = &[
+  "securitiesClassTitle",
+  "acquiredDate",
+  "natureOfAcquisitionTransaction",
+  "nameOfPersonfromWhomAcquired",
+  "isGiftTransaction",
+  "amountOfSecuritiesAcquired",
+  "paymentDate",
+  "natureOfPayment",
+];
+
+const SECURITIES_SOLD_ELEMENTS: &[&str] = &[
+  "sellerDetails",
+  "securitiesClassTitle",
+  "saleDate",
+  "amountOfSecuritiesSold",
+  "grossProceeds",
+];
+
+#[napi]
+pub fn parse_form144(
+  env: Env,
+  form: String,
+  options: Option<ParseOptions>,
+) -> Result<Form144, Error> {
+  let doc = XMLDoc::parse(&form).map_err(|e| ParseError::from(e).into_napi(env))?;
+  let diagnostics = &Diagnostics::new(ParseOptions::mode(&options));
+  parse_form144_document(&doc.root_element(), diagnostics).map_err(|e| e.into_napi(env))
+}
+
+fn parse_form144_document(root: &Node, diagnostics: &Diagnostics) -> Result<Form144, ParseError> {
+  let header_node = find_required(root, "headerData")?;
+  let submission_type =
+    parse_recoverable::<Form144SubmissionType>(&header_node, "submissionType", diagnostics)?;
+  let filer_info_node = header_node
+    .children()
+    .find(|node| node.has_tag_name("filerInfo"));
+  let filer_cik = filer_info_node
+    .and_then(|node| node.descendants().find(|node| node.has_tag_name("cik")))
+    .and_then(|node| node.text())
+    .map(ToString::to_string);
+  let live_test_flag = filer_info_node
+    .map(|node| parse_optional::<LiveTestFlag>(&node, "liveTestFlag", diagnostics))
+    .transpose()?
+    .flatten();
+
+  let form_data_node = find_required(root, "formData")?;
+  diagnostics.unknown_elements(&form_data_node, FORM_DATA_ELEMENTS)?;
+  let issuer_node = find_required(&form_data_node, "issuerInfo")?;
+  diagnostics.unknown_elements(&issuer_node, ISSUER_INFO_ELEMENTS)?;
+  let issuer = parse_issuer(&issuer_node, diagnostics)?;
+  let seller = parse_seller(&issuer_node, diagnostics)?;
+  let securities_to_be_sold = parse_securities_to_be_sold(&form_data_node, diagnostics)?;
+  let securities_acquired = parse_securities_acquired(&form_data_node, diagnostics)?;
+  let nothing_to_report_past_three_months = parse_optional::<bool>(
+    &form_data_node,
+    "nothingToReportFlagOnSecuritiesSoldInPast3Months",
+    diagnostics,
+  )?;
+  let sales_past_three_months = parse_past_sales(&form_data_node, diagnostics)?;
+  let remarks = parse_string::<String>(&form_data_node, "remarks");
+
+  let signature_node = form_data_node
+    .children()
+    .find(|node| node.has_tag_name("noticeSignature"));
+  let notice_date = signature_node
+    .map(|node| parse_optional::<UsDate>(&node, "noticeDate", diagnostics))
+    .transpose()?
+    .flatten();
+  let plan_adoption_dates = signature_node
+    .into_iter()
+    .flat_map(|node| node.children())
+    .filter(|node| node.has_tag_name("planAdoptionDates"))
+    .map(|node| parse_rows(&node, "planAdoptionDate", diagnostics, parse_date))
+    .collect::<Result<Vec<_>, _>>()?
+    .into_iter()
+    .flatten()
+    .collect();
+  let signature = signature_node.and_then(|node| parse_string::<String>(&node, "signature"));
+
+  Ok(Form144 {
+    submission_type,
+    filer_cik,
+    live_test_flag,
+    issuer,
+    seller,
+    securities_to_be_sold,
+    securities_acquired,
+    nothing_to_report_past_three_months,
+    sales_past_three_months,
+    remarks,
+    notice_date,
+    plan_adoption_dates,
+    signature,
+    warnings: diagnostics.take_warnings(),
+  })
+}
+
+fn parse_issuer(node: &Node, diagnostics: &Diagnostics) -> Result<Form144Issuer, ParseError> {
+  let cik = parse_field::<String>(node, "issuerCik", diagnostics)?;
+  let name = parse_field::<String>(node, "issuerName", diagnostics)?;
+  let sec_file_number = parse_string::<String>(node, "secFileNumber");
+  let address = parse_address(node, "issuerAddress");
+  let contact_phone = parse_string::<String>(node, "issuerContactPhone");
+
+  Ok(Form144Issuer {
+    cik,
+    name,
+    sec_file_number,
+    address,
+    contact_phone,
+  })
+}
+
+fn parse_seller(node: &Node, diagnostics: &Diagnostics) -> Result<Form144Seller, ParseError> {
+  let name = parse_field::<String>(
+    node,
+    "nameOfPersonForWhoseAccountTheSecuritiesAreToBeSold",
+    diagnostics,
+  )?;
+  let relationships_to_issuer = node
+    .children()
+    .filter(|node| node.has_tag_name("relationshipsToIssuer"))
+    .flat_map(|node| node.children())
+    .filter(|node| node.has_tag_name("relationshipToIssuer"))
+    .filter_map(|node| node.text())
+    .map(ToString::to_string)
+    .collect();
+
+  Ok(Form144Seller {
+    name,
+    relationships_to_issuer,
+  })
+}
+
+fn parse_securities_to_be_sold(
+  node: &Node,
+  diagnostics: &Diagnostics,
+) -> Result<Vec<SecuritiesToBeSold>, ParseError> {
+  parse_rows(node, "securitiesInformation", diagnostics, |row_node| {
+    diagnostics.unknown_elements(row_node, SECURITIES_INFORMATION_ELEMENTS)?;
+    let class_title = parse_required::<String>(row_node, "securitiesClassTitle")?;
+    let broker = parse_broker(row_node)?;
+    let units_to_be_sold = parse_required::<f64>(row_node, "noOfUnitsSold")?;
+    let aggregate_market_value = parse_required::<f64>(row_node, "aggregateMarketValue")?;
+    let units_outstanding = parse_optional::<f64>(row_node, "noOfUnitsOutstanding", diagnostics)?;
+    let approximate_sale_date = parse_optional::<UsDate>(row_node, "approxSaleDate", diagnostics)?;
+    let exchange = parse_string::<String>(row_node, "securitiesExchangeName");
+
+    Ok(SecuritiesToBeSold {
+      class_title,
+      broker,
+      units_to_be_sold,
+      aggregate_market_value,
+      units_outstanding,
+      approximate_sale_date,
+      exchange,
+    })
+  })
+}
+
+fn parse_broker(node: &Node) -> Result<Option<Broker>, ParseError> {
+  node
+    .children()
+    .find(|node| node.has_tag_name("brokerOrMarketmakerDetails"))
+    .map(|broker_node| {
+      let name = parse_required::<String>(&broker_node, "name")?;
+      let address = parse_address(&broker_node, "address");
+
+      Ok(Broker { name, address })
+    })
+    .transpose()
+}
+
+fn parse_securities_acquired(
+  node: &Node,
+  diagnostics: &Diagnostics,
+) -> Result<Vec<SecuritiesAcquired>, ParseError> {
+  parse_rows(node, "securitiesToBeSold", diagnostics, |row_node| {
+    diagnostics.unknown_elements(row_node, SECURITIES_TO_BE_SOLD_ELEMENTS)?;
+    let class_title = parse_required::<String>(row_node, "securitiesClassTitle")?;
+    let acquired_date = parse_optional::<UsDate>(row_node, "acquiredDate", diagnostics)?;
+    let nature_of_acquisition = parse_string::<String>(row_node, "natureOfAcquisitionTransaction");
+    let acquired_from = parse_string::<String>(row_node, "nameOfPersonfromWhomAcquired");
+    let is_gift = parse_optional::<bool>(row_node, "isGiftTransaction", diagnostics)?;
+    let amount_acquired = parse_required::<f64>(row_node, "amountOfSecuritiesAcquired")?;
+    let payment_date = parse_optional::<UsDate>(row_node, "paymentDate", diagnostics)?;
+    let nature_of_payment = parse_string::<String>(row_node, "natureOfPayment");
+
+    Ok(SecuritiesAcquired {
+      class_title,
+      acquired_date,
+      nature_of_acquisition,
+      acquired_from,
+      is_gift,
+      amount_acquired,
+      payment_date,
+      nature_of_payment,
+    })
+  })
+}
+
+fn parse_past_sales(node: &Node, diagnostics: &Diagnostics) -> Result<Vec<PastSale>, ParseError> {
+  parse_rows(
+    node,
+    "securitiesSoldInPast3Months",
+    diagnostics,
+    |row_node| {
+      diagnostics.unknown_elements(row_node, SECURITIES_SOLD_ELEMENTS)?;
+      let seller_node = row_node
+        .children()
+        .find(|node| node.has_tag_name("sellerDetails"));
+      let seller_name = seller_node.and_then(|node| parse_string::<String>(&node, "name"));
+      let seller_address = seller_node.and_then(|node| parse_address(&node, "address"));
+      let class_title = parse_required::<String>(row_node, "securitiesClassTitle")?;
+      let sale_date = parse_optional::<UsDate>(row_node, "saleDate", diagnostics)?;
+      let amount_sold = parse_required::<f64>(row_node, "amountOfSecuritiesSold")?;
+      let gross_proceeds = parse_optional::<f64>(row_node, "grossProceeds", diagnostics)?;
+
+      Ok(PastSale {
+        seller_name,
+        seller_address,
+        class_title,
+        sale_date,
+        amount_sold,
+        gross_proceeds,
+      })
+    },
+  )
+}
+
+fn parse_address(node: &Node, tag: &str) -> Option<Form144Address> {
+  node
+    .children()
+    .find(|node| node.has_tag_name(tag))
+    .map(|address_node| Form144Address {
+      street1: parse_string::<String>(&address_node, "street1"),
+      street2: parse_string::<String>(&address_node, "street2"),
+      city: parse_string::<String>(&address_node, "city"),
+      state_or_country: parse_string::<String>(&address_node, "stateOrCountry"),
+      zip_code: parse_string::<String>(&address_node, "zipCode"),
+    })
+}
+
+fn parse_date(node: &Node) -> Result<String, ParseError> {
+  let text = node.text().unwrap_or_default();
+  UsDate::parse(text).map_err(|reason| ParseError::invalid(node, text, reason))
+}
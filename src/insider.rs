@@ -0,0 +1,136 @@
+use napi_derive::napi;
+
+use crate::ownership::{DirectOrIndirect, OwnershipForm, OwnershipTableKind, TransactionCode};
+
+#[napi(object)]
+pub struct InsiderSummary {
+  pub owner_cik: String,
+  pub owner_name: Option<String>,
+  /// Non-derivative shares bought on the open market (code P).
+  pub shares_purchased: f64,
+  /// Non-derivative shares sold on the open market (code S).
+  pub shares_sold: f64,
+  pub net_shares: f64,
+  pub purchase_value: f64,
+  pub sale_value: f64,
+  /// Dollar value of all open market purchases and sales.
+  pub total_value: f64,
+  pub average_purchase_price: Option<f64>,
+  pub average_sale_price: Option<f64>,
+  /// The last reported non-derivative holding of each security and ownership nature.
+  pub shares_owned_following: Vec<SecurityHolding>,
+  /// Derivative shares exercised or converted (codes M, X and O).
+  pub shares_exercised: f64,
+  pub option_exercises: u32,
+  pub open_market_sales: u32,
+  /// Whether the form reports transactions made under a Rule 10b5-1 plan.
+  pub aff10b5_one: bool,
+}
+
+#[napi(object)]
+#[derive(Clone)]
+pub struct SecurityHolding {
+  pub security_title: Option<String>,
+  #[napi(ts_type = "'D' | 'I'")]
+  pub direct_or_indirect: Option<DirectOrIndirect>,
+  pub shares: f64,
+}
+
+#[derive(Default)]
+struct Trades {
+  shares: f64,
+  value: f64,
+  priced_shares: f64,
+  count: u32,
+}
+
+impl Trades {
+  fn add(&mut self, shares: f64, price: Option<f64>) {
+    self.shares += shares;
+    self.count += 1;
+    if let Some(price) = price {
+      self.value += shares * price;
+      self.priced_shares += shares;
+    }
+  }
+
+  fn average_price(&self) -> Option<f64> {
+    (self.priced_shares > 0.0).then(|| self.value / self.priced_shares)
+  }
+}
+
+/// Summarizes the open market activity of a Form 4 for each reporting owner. Ownership forms do
+/// not attribute transactions to individual owners, so joint filers share the same figures.
+#[napi]
+pub fn summarize_insider_activity(form: OwnershipForm) -> Vec<InsiderSummary> {
+  let mut purchases = Trades::default();
+  let mut sales = Trades::default();
+  let mut exercises = Trades::default();
+  let mut holdings: Vec<SecurityHolding> = vec![];
+
+  for (table, _, fields) in form.table_rows() {
+    match (table, fields.transaction_code, fields.shares) {
+      (
+        OwnershipTableKind::NonDerivativeTransaction,
+        Some(TransactionCode::OpenMarketPurchase),
+        Some(shares),
+      ) => purchases.add(shares, fields.price_per_share),
+      (
+        OwnershipTableKind::NonDerivativeTransaction,
+        Some(TransactionCode::OpenMarketSale),
+        Some(shares),
+      ) => sales.add(shares, fields.price_per_share),
+      (
+        OwnershipTableKind::DerivativeTransaction,
+        Some(
+          TransactionCode::ExemptExercise
+          | TransactionCode::InTheMoneyExercise
+          | TransactionCode::OutOfTheMoneyExercise,
+        ),
+        Some(shares),
+      ) => exercises.add(shares, None),
+      _ => {}
+    }
+
+    if let (
+      OwnershipTableKind::NonDerivativeTransaction | OwnershipTableKind::NonDerivativeHolding,
+      Some(shares),
+    ) = (table, fields.shares_owned_following)
+    {
+      let security_title = fields.security_title.map(|title| title.trim().to_owned());
+      match holdings.iter_mut().find(|holding| {
+        holding.security_title == security_title
+          && holding.direct_or_indirect == fields.direct_or_indirect
+      }) {
+        Some(holding) => holding.shares = shares,
+        None => holdings.push(SecurityHolding {
+          security_title,
+          direct_or_indirect: fields.direct_or_indirect,
+          shares,
+        }),
+      }
+    }
+  }
+
+  form
+    .reporting_owners
+    .iter()
+    .map(|owner| InsiderSummary {
+      owner_cik: owner.id.cik.clone(),
+      owner_name: owner.id.name.clone(),
+      shares_purchased: purchases.shares,
+      shares_sold: sales.shares,
+      net_shares: purchases.shares - sales.shares,
+      purchase_value: purchases.value,
+      sale_value: sales.value,
+      total_value: purchases.value + sales.value,
+      average_purchase_price: purchases.average_price(),
+      average_sale_price: sales.average_price(),
+      shares_owned_following: holdings.clone(),
+      shares_exercised: exercises.shares,
+      option_exercises: exercises.count,
+      open_market_sales: sales.count,
+      aff10b5_one: form.aff10b5_one.unwrap_or(false),
+    })
+    .collect()
+}
@@ -1,10 +1,227 @@
 use napi::{Env, JsUnknown};
+use napi_derive::napi;
 use roxmltree::Node;
+use std::cell::RefCell;
 
+use crate::error::{node_path, ParseError};
+
+/// Declares a fieldless enum that converts to and from the code string used in filings. Fields of
+/// these types are typed as string unions with `#[napi(ts_type = "...")]`.
+macro_rules! string_union {
+  (
+    $(#[$meta:meta])*
+    pub enum $name:ident {
+      $($(#[$variant_meta:meta])* $variant:ident = $code:literal,)+
+    }
+  ) => {
+    $(#[$meta])*
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum $name {
+      $($(#[$variant_meta])* $variant,)+
+    }
+
+    impl $name {
+      pub fn as_str(&self) -> &'static str {
+        match self {
+          $($name::$variant => $code,)+
+        }
+      }
+    }
+
+    impl std::str::FromStr for $name {
+      type Err = String;
+
+      fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+          $($code => Ok($name::$variant),)+
+          _ => Err(format!("unknown {} code: {}", stringify!($name), s)),
+        }
+      }
+    }
+
+    impl crate::ParseFromString for $name {
+      type Output = $name;
+
+      fn parse(s: &str) -> Result<Self::Output, String> {
+        s.trim().parse()
+      }
+    }
+
+    impl napi::bindgen_prelude::TypeName for $name {
+      fn type_name() -> &'static str {
+        stringify!($name)
+      }
+
+      fn value_type() -> napi::ValueType {
+        napi::ValueType::String
+      }
+    }
+
+    impl napi::bindgen_prelude::ToNapiValue for $name {
+      unsafe fn to_napi_value(
+        env: napi::sys::napi_env,
+        val: Self,
+      ) -> napi::Result<napi::sys::napi_value> {
+        <&str as napi::bindgen_prelude::ToNapiValue>::to_napi_value(env, val.as_str())
+      }
+    }
+
+    impl napi::bindgen_prelude::FromNapiValue for $name {
+      unsafe fn from_napi_value(
+        env: napi::sys::napi_env,
+        napi_val: napi::sys::napi_value,
+      ) -> napi::Result<Self> {
+        <String as napi::bindgen_prelude::FromNapiValue>::from_napi_value(env, napi_val)?
+          .parse()
+          .map_err(napi::Error::from_reason)
+      }
+    }
+  };
+}
+
+pub mod error;
 pub mod form_13f;
+pub mod form_13f_aggregate;
+pub mod form_13f_amendment;
+pub mod form_13f_diff;
+pub mod form_13f_validation;
+pub mod form_13f_values;
+pub mod form_144;
+pub mod insider;
 pub mod ownership;
+pub mod ownership_amendment;
+pub mod ownership_export;
+pub mod position_ledger;
+pub mod reporting_owners;
+pub mod schedule_13dg;
+pub mod security_identifiers;
 pub mod xbrl;
 
+#[napi(string_enum)]
+#[derive(Default, PartialEq)]
+pub enum ParseMode {
+  /// Fail on the first malformed or unknown element, or any other value that would be dropped.
+  Strict,
+  /// Skip malformed rows, default missing values and report them in the result's warnings.
+  /// Only XML syntax errors and missing document sections still fail. This is the default.
+  #[default]
+  Lenient,
+}
+
+#[napi(object)]
+pub struct ParseOptions {
+  pub mode: Option<ParseMode>,
+  /// Ownership forms only: attach footnote texts to the values that reference them, and list
+  /// the fields each footnote annotates.
+  pub resolve_footnotes: Option<bool>,
+}
+
+impl ParseOptions {
+  fn mode(options: &Option<ParseOptions>) -> ParseMode {
+    options
+      .as_ref()
+      .and_then(|options| options.mode)
+      .unwrap_or_default()
+  }
+
+  fn resolve_footnotes(options: &Option<ParseOptions>) -> bool {
+    options
+      .as_ref()
+      .and_then(|options| options.resolve_footnotes)
+      .unwrap_or_default()
+  }
+}
+
+#[napi(object)]
+pub struct Diagnostic {
+  pub code: String,
+  pub path: Option<String>,
+  pub message: String,
+  pub line: u32,
+  pub column: u32,
+}
+
+impl Diagnostic {
+  fn new(code: &str, node: &Node, message: String) -> Self {
+    let pos = node.document().text_pos_at(node.range().start);
+    Diagnostic {
+      code: code.to_owned(),
+      path: Some(node_path(node)),
+      message,
+      line: pos.row,
+      column: pos.col,
+    }
+  }
+}
+
+impl From<ParseError> for Diagnostic {
+  fn from(err: ParseError) -> Self {
+    let (line, column) = err.position();
+    Diagnostic {
+      code: err.code().to_owned(),
+      path: err.path().map(ToString::to_string),
+      message: err.to_string(),
+      line,
+      column,
+    }
+  }
+}
+
+/// Collects the non-fatal issues found while parsing a document.
+struct Diagnostics {
+  mode: ParseMode,
+  warnings: RefCell<Vec<Diagnostic>>,
+}
+
+impl Diagnostics {
+  fn new(mode: ParseMode) -> Self {
+    Diagnostics {
+      mode,
+      warnings: RefCell::new(vec![]),
+    }
+  }
+
+  fn warn(&self, diagnostic: Diagnostic) {
+    self.warnings.borrow_mut().push(diagnostic);
+  }
+
+  /// Records a recoverable error in lenient mode, or returns it in strict mode.
+  fn recover(&self, err: ParseError) -> Result<(), ParseError> {
+    match self.mode {
+      ParseMode::Strict => Err(err),
+      ParseMode::Lenient => {
+        self.warn(Diagnostic::from(err));
+        Ok(())
+      }
+    }
+  }
+
+  fn skip_row(&self, row: &Node, err: ParseError) -> Result<(), ParseError> {
+    match self.mode {
+      ParseMode::Strict => Err(err),
+      ParseMode::Lenient => {
+        let message = format!("skipped {}: {}", node_path(row), err);
+        self.warn(Diagnostic::new("SKIPPED_ROW", row, message));
+        Ok(())
+      }
+    }
+  }
+
+  /// Reports children of `node` that are not in `known`, failing in strict mode.
+  fn unknown_elements(&self, node: &Node, known: &[&str]) -> Result<(), ParseError> {
+    for child_node in node.children().filter(|node| node.is_element()) {
+      if !known.contains(&child_node.tag_name().name()) {
+        self.recover(ParseError::unknown(&child_node))?;
+      }
+    }
+    Ok(())
+  }
+
+  fn take_warnings(&self) -> Vec<Diagnostic> {
+    self.warnings.take()
+  }
+}
+
 fn parse_date(node: &Node, tag: &str) -> Option<String> {
   node
     .children()
@@ -13,14 +230,35 @@ fn parse_date(node: &Node, tag: &str) -> Option<String> {
     .map(ToString::to_string)
 }
 
-fn parse_ints(node: &Node, tag: &str) -> Vec<i32> {
-  node
-    .children()
-    .filter(|node| node.has_tag_name(tag))
-    .filter_map(|node| node.text())
-    .flat_map(|text| text.split(','))
-    .filter_map(|s| s.trim().parse().ok())
-    .collect()
+fn parse_ints(node: &Node, tag: &str, diagnostics: &Diagnostics) -> Result<Vec<i32>, ParseError> {
+  let mut ints = vec![];
+  for int_node in node.children().filter(|node| node.has_tag_name(tag)) {
+    let text = int_node.text().unwrap_or_default();
+    for s in text.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+      match <i32 as ParseFromString>::parse(s) {
+        Ok(value) => ints.push(value),
+        Err(reason) => diagnostics.recover(ParseError::invalid(&int_node, s, reason))?,
+      }
+    }
+  }
+  Ok(ints)
+}
+
+/// Parses every `tag` child of `node`, skipping rows that fail in lenient mode.
+fn parse_rows<'a, 'input: 'a, T>(
+  node: &Node<'a, 'input>,
+  tag: &str,
+  diagnostics: &Diagnostics,
+  parse: impl Fn(&Node<'a, 'input>) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+  let mut rows = vec![];
+  for row_node in node.children().filter(|node| node.has_tag_name(tag)) {
+    match parse(&row_node) {
+      Ok(row) => rows.push(row),
+      Err(err) => diagnostics.skip_row(&row_node, err)?,
+    }
+  }
+  Ok(rows)
 }
 trait ParseFromString {
   type Output;
@@ -39,7 +277,8 @@ impl ParseFromString for i32 {
   type Output = i32;
 
   fn parse(s: &str) -> Result<Self::Output, String> {
-    s.parse::<i32>()
+    s.trim()
+      .parse::<i32>()
       .map_err(|_| format!("failed to parse i32 from: {}", s))
   }
 }
@@ -48,11 +287,58 @@ impl ParseFromString for i64 {
   type Output = i64;
 
   fn parse(s: &str) -> Result<Self::Output, String> {
-    s.parse::<i64>()
+    s.trim()
+      .parse::<i64>()
       .map_err(|_| format!("failed to parse i64 from: {}", s))
   }
 }
 
+impl ParseFromString for f64 {
+  type Output = f64;
+
+  fn parse(s: &str) -> Result<Self::Output, String> {
+    s.trim()
+      .parse::<f64>()
+      .map_err(|_| format!("failed to parse f64 from: {}", s))
+  }
+}
+
+/// A `YYYY-MM-DD` date, dropping any time zone offset that follows it.
+struct IsoDate;
+
+impl ParseFromString for IsoDate {
+  type Output = String;
+
+  fn parse(s: &str) -> Result<Self::Output, String> {
+    let date = s.trim().get(..10).unwrap_or_default();
+    let mut parts = date.split('-').map(|part| part.parse::<u32>().ok());
+    match (parts.next(), parts.next(), parts.next()) {
+      (Some(Some(_)), Some(Some(1..=12)), Some(Some(1..=31))) if date.len() == 10 => {
+        Ok(date.to_owned())
+      }
+      _ => Err(format!("failed to parse date from: {}", s)),
+    }
+  }
+}
+
+/// An `MM/DD/YYYY` or `MM-DD-YYYY` date, converted to `YYYY-MM-DD`.
+struct UsDate;
+
+impl ParseFromString for UsDate {
+  type Output = String;
+
+  fn parse(s: &str) -> Result<Self::Output, String> {
+    let mut parts = s.trim().split(['/', '-']);
+    match (parts.next(), parts.next(), parts.next(), parts.next()) {
+      (Some(month), Some(day), Some(year), None) if month.len() == 2 && day.len() == 2 => {
+        IsoDate::parse(&format!("{}-{}-{}", year, month, day))
+          .map_err(|_| format!("failed to parse date from: {}", s))
+      }
+      _ => Err(format!("failed to parse date from: {}", s)),
+    }
+  }
+}
+
 impl ParseFromString for bool {
   type Output = bool;
 
@@ -73,6 +359,80 @@ fn parse_string<T: ParseFromString>(node: &Node, tag: &str) -> Option<T::Output>
     .and_then(|text| T::parse(text).ok())
 }
 
+fn find_required<'a, 'input>(
+  node: &Node<'a, 'input>,
+  tag: &str,
+) -> Result<Node<'a, 'input>, ParseError> {
+  node
+    .children()
+    .find(|node| node.has_tag_name(tag))
+    .ok_or_else(|| ParseError::missing(node, tag))
+}
+
+fn parse_required<T: ParseFromString>(node: &Node, tag: &str) -> Result<T::Output, ParseError> {
+  let tag_node = node
+    .children()
+    .find(|node| node.has_tag_name(tag))
+    .ok_or_else(|| ParseError::missing(node, tag))?;
+  let text = tag_node
+    .text()
+    .ok_or_else(|| ParseError::missing(node, tag))?;
+  T::parse(text).map_err(|reason| ParseError::invalid(&tag_node, text, reason))
+}
+
+/// Like `parse_required`, but leaves the value unset in lenient mode.
+fn parse_recoverable<T: ParseFromString>(
+  node: &Node,
+  tag: &str,
+  diagnostics: &Diagnostics,
+) -> Result<Option<T::Output>, ParseError> {
+  match parse_required::<T>(node, tag) {
+    Ok(value) => Ok(Some(value)),
+    Err(err) => {
+      diagnostics.recover(err)?;
+      Ok(None)
+    }
+  }
+}
+
+/// Like `parse_required`, but falls back to the default value in lenient mode.
+fn parse_field<T: ParseFromString>(
+  node: &Node,
+  tag: &str,
+  diagnostics: &Diagnostics,
+) -> Result<T::Output, ParseError>
+where
+  T::Output: Default,
+{
+  match parse_required::<T>(node, tag) {
+    Ok(value) => Ok(value),
+    Err(err) => {
+      diagnostics.recover(err)?;
+      Ok(Default::default())
+    }
+  }
+}
+
+fn parse_optional<T: ParseFromString>(
+  node: &Node,
+  tag: &str,
+  diagnostics: &Diagnostics,
+) -> Result<Option<T::Output>, ParseError> {
+  let Some(tag_node) = node.children().find(|node| node.has_tag_name(tag)) else {
+    return Ok(None);
+  };
+  let Some(text) = tag_node.text().filter(|text| !text.trim().is_empty()) else {
+    return Ok(None);
+  };
+  match T::parse(text) {
+    Ok(value) => Ok(Some(value)),
+    Err(reason) => {
+      diagnostics.recover(ParseError::invalid(&tag_node, text, reason))?;
+      Ok(None)
+    }
+  }
+}
+
 fn parse_value(env: Env, value_str: &str) -> napi::Result<JsUnknown> {
   let str = value_str.trim();
   if let Ok(value) = str.parse::<bool>() {
@@ -1,10 +1,14 @@
 use napi::Env;
 use napi::Error;
-use napi::JsUnknown;
 use napi_derive::napi;
 use roxmltree::{Document as XMLDoc, Node};
+use std::collections::HashMap;
 
-use crate::{parse_string, parse_value};
+use crate::error::{node_path, ParseError};
+use crate::{
+  find_required, parse_field, parse_optional, parse_required, parse_rows, parse_string, Diagnostic,
+  Diagnostics, IsoDate, ParseFromString, ParseOptions,
+};
 
 #[napi(object)]
 pub struct OwnershipForm {
@@ -24,6 +28,7 @@ pub struct OwnershipForm {
   pub footnotes: Vec<Footnote>,
   pub remarks: Option<String>,
   pub owner_signatures: Vec<OwnerSignature>,
+  pub warnings: Vec<Diagnostic>,
 }
 
 #[napi(object)]
@@ -34,6 +39,7 @@ pub struct Issuer {
 }
 
 #[napi(object)]
+#[derive(Clone)]
 pub struct ReportingOwner {
   pub id: ReportingOwnerID,
   pub address: Option<ReportingOwnerAddress>,
@@ -41,6 +47,7 @@ pub struct ReportingOwner {
 }
 
 #[napi(object, js_name = "ReportingOwnerID")]
+#[derive(Clone)]
 pub struct ReportingOwnerID {
   pub cik: String,
   pub ccc: Option<String>,
@@ -48,6 +55,7 @@ pub struct ReportingOwnerID {
 }
 
 #[napi(object)]
+#[derive(Clone)]
 pub struct ReportingOwnerAddress {
   pub street1: Option<String>,
   pub street2: Option<String>,
@@ -58,6 +66,7 @@ pub struct ReportingOwnerAddress {
 }
 
 #[napi(object)]
+#[derive(Clone)]
 pub struct ReportingOwnerRelationship {
   pub is_director: Option<bool>,
   pub is_officer: Option<bool>,
@@ -68,117 +77,146 @@ pub struct ReportingOwnerRelationship {
 }
 
 #[napi(object)]
+#[derive(Clone, PartialEq)]
 pub struct NonDerivativeTable {
   pub transactions: Vec<NonDerivativeTransaction>,
   pub holdings: Vec<NonDerivativeHolding>,
 }
 
 #[napi(object)]
+#[derive(Clone, PartialEq)]
 pub struct DerivativeTable {
   pub transactions: Vec<DerivativeTransaction>,
   pub holdings: Vec<DerivativeHolding>,
 }
 
+/// Identifies one of the four row types of an ownership form.
+#[napi(string_enum)]
+pub enum OwnershipTableKind {
+  NonDerivativeTransaction,
+  NonDerivativeHolding,
+  DerivativeTransaction,
+  DerivativeHolding,
+}
+
 #[napi(object)]
+#[derive(Clone, PartialEq)]
 pub struct NonDerivativeTransaction {
-  pub security_title: Option<ValueFootnotes>,
-  pub transaction_date: Option<ValueFootnotes>,
-  pub deemed_execution_date: Option<ValueFootnotes>,
+  pub security_title: Option<StringFootnotes>,
+  pub transaction_date: Option<DateFootnotes>,
+  pub deemed_execution_date: Option<DateFootnotes>,
   pub transaction_coding: Option<TransactionCoding>,
-  pub transaction_timeliness: Option<ValueFootnotes>,
+  pub transaction_timeliness: Option<TimelinessFootnotes>,
   pub transaction_amounts: Option<TransactionAmounts>,
   pub post_transaction_amounts: Option<PostTransactionAmounts>,
   pub ownership_nature: Option<OwnershipNature>,
 }
 
 #[napi(object)]
+#[derive(Clone, PartialEq)]
 pub struct DerivativeTransaction {
-  pub security_title: Option<ValueFootnotes>,
-  pub conversion_or_exercise_price: Option<ValueFootnotes>,
-  pub deemed_execution_date: Option<ValueFootnotes>,
-  pub transaction_date: Option<ValueFootnotes>,
+  pub security_title: Option<StringFootnotes>,
+  pub conversion_or_exercise_price: Option<DecimalFootnotes>,
+  pub deemed_execution_date: Option<DateFootnotes>,
+  pub transaction_date: Option<DateFootnotes>,
   pub transaction_coding: Option<TransactionCoding>,
-  pub transaction_timeliness: Option<ValueFootnotes>,
+  pub transaction_timeliness: Option<TimelinessFootnotes>,
   pub transaction_amounts: Option<DerivativeTransactionAmounts>,
-  pub exercise_date: Option<ValueFootnotes>,
-  pub expiration_date: Option<ValueFootnotes>,
+  pub exercise_date: Option<DateFootnotes>,
+  pub expiration_date: Option<DateFootnotes>,
   pub underlying_security: Option<UnderlyingSecurity>,
   pub post_transaction_amounts: Option<PostTransactionAmounts>,
   pub ownership_nature: Option<OwnershipNature>,
 }
 
 #[napi(object)]
+#[derive(Clone, PartialEq)]
 pub struct NonDerivativeHolding {
-  pub security_title: Option<ValueFootnotes>,
+  pub security_title: Option<StringFootnotes>,
   pub transaction_coding: Option<HoldingCoding>,
   pub post_transaction_amounts: Option<PostTransactionAmounts>,
   pub ownership_nature: Option<OwnershipNature>,
 }
 
 #[napi(object)]
+#[derive(Clone, PartialEq)]
 pub struct DerivativeHolding {
-  pub security_title: Option<ValueFootnotes>,
-  pub conversion_or_exercise_price: Option<ValueFootnotes>,
+  pub security_title: Option<StringFootnotes>,
+  pub conversion_or_exercise_price: Option<DecimalFootnotes>,
   pub transaction_coding: Option<HoldingCoding>,
-  pub exercise_date: Option<ValueFootnotes>,
-  pub expiration_date: Option<ValueFootnotes>,
+  pub exercise_date: Option<DateFootnotes>,
+  pub expiration_date: Option<DateFootnotes>,
   pub underlying_security: Option<UnderlyingSecurity>,
   pub post_transaction_amounts: Option<PostTransactionAmounts>,
   pub ownership_nature: Option<OwnershipNature>,
 }
 
 #[napi(object)]
+#[derive(Clone, PartialEq)]
 pub struct TransactionCoding {
   pub form_type: Option<String>,
-  pub transaction_code: Option<String>,
+  #[napi(
+    ts_type = "'P' | 'S' | 'V' | 'A' | 'D' | 'F' | 'I' | 'M' | 'C' | 'E' | 'H' | 'O' | 'X' | 'G' | 'L' | 'W' | 'Z' | 'J' | 'K' | 'U'"
+  )]
+  pub transaction_code: Option<TransactionCode>,
   pub equity_swap_involved: Option<bool>,
   pub footnote_ids: Option<Vec<String>>,
+  pub footnotes: Option<Vec<String>>,
 }
 
 #[napi(object)]
+#[derive(Clone, PartialEq)]
 pub struct HoldingCoding {
   pub form_type: Option<String>,
   pub footnote_ids: Option<Vec<String>>,
+  pub footnotes: Option<Vec<String>>,
 }
 
 #[napi(object)]
+#[derive(Clone, PartialEq)]
 pub struct TransactionAmounts {
-  pub shares: Option<ValueFootnotes>,
-  pub price_per_share: Option<ValueFootnotes>,
-  pub acquired_disposed_code: Option<ValueFootnotes>,
+  pub shares: Option<DecimalFootnotes>,
+  pub price_per_share: Option<DecimalFootnotes>,
+  pub acquired_disposed_code: Option<AcquiredDisposedFootnotes>,
 }
 
 #[napi(object)]
+#[derive(Clone, PartialEq)]
 pub struct DerivativeTransactionAmounts {
-  pub shares: Option<ValueFootnotes>,
-  pub price_per_share: Option<ValueFootnotes>,
-  pub total_value: Option<ValueFootnotes>,
-  pub acquired_disposed_code: Option<ValueFootnotes>,
+  pub shares: Option<DecimalFootnotes>,
+  pub price_per_share: Option<DecimalFootnotes>,
+  pub total_value: Option<DecimalFootnotes>,
+  pub acquired_disposed_code: Option<AcquiredDisposedFootnotes>,
 }
 
 #[napi(object)]
+#[derive(Clone, PartialEq)]
 pub struct UnderlyingSecurity {
-  pub title: Option<ValueFootnotes>,
-  pub shares: Option<ValueFootnotes>,
-  pub value: Option<ValueFootnotes>,
+  pub title: Option<StringFootnotes>,
+  pub shares: Option<DecimalFootnotes>,
+  pub value: Option<DecimalFootnotes>,
 }
 
 #[napi(object)]
+#[derive(Clone, PartialEq)]
 pub struct PostTransactionAmounts {
-  pub shares_owned_following_transaction: Option<ValueFootnotes>,
-  pub value_owned_following_transaction: Option<ValueFootnotes>,
+  pub shares_owned_following_transaction: Option<DecimalFootnotes>,
+  pub value_owned_following_transaction: Option<DecimalFootnotes>,
 }
 
 #[napi(object)]
+#[derive(Clone, PartialEq)]
 pub struct OwnershipNature {
-  pub direct_or_indirect_ownership: Option<ValueFootnotes>,
-  pub nature_of_ownership: Option<ValueFootnotes>,
+  pub direct_or_indirect_ownership: Option<DirectOrIndirectFootnotes>,
+  pub nature_of_ownership: Option<StringFootnotes>,
 }
 
 #[napi(object)]
 pub struct Footnote {
   pub id: Option<String>,
   pub note: Option<String>,
+  /// Paths of the fields annotated by this footnote, set when resolving footnotes.
+  pub references: Option<Vec<String>>,
 }
 
 #[napi(object)]
@@ -187,37 +225,726 @@ pub struct OwnerSignature {
   pub date: String,
 }
 
+/// A numeric value such as a share count or price. `value` is absent when the filing gives only
+/// footnotes in its place.
 #[napi(object)]
-pub struct ValueFootnotes {
-  pub value: Option<JsUnknown>,
+#[derive(Clone, PartialEq)]
+pub struct DecimalFootnotes {
+  pub value: Option<f64>,
   pub footnote_ids: Option<Vec<String>>,
+  /// Texts of the referenced footnotes, set when resolving footnotes.
+  pub footnotes: Option<Vec<String>>,
+}
+
+/// A `YYYY-MM-DD` date. `value` is absent when the filing gives only footnotes in its place.
+#[napi(object)]
+#[derive(Clone, PartialEq)]
+pub struct DateFootnotes {
+  pub value: Option<String>,
+  pub footnote_ids: Option<Vec<String>>,
+  /// Texts of the referenced footnotes, set when resolving footnotes.
+  pub footnotes: Option<Vec<String>>,
+}
+
+/// A text value such as a security title. `value` is absent when the filing gives only footnotes
+/// in its place.
+#[napi(object)]
+#[derive(Clone, PartialEq)]
+pub struct StringFootnotes {
+  pub value: Option<String>,
+  pub footnote_ids: Option<Vec<String>>,
+  /// Texts of the referenced footnotes, set when resolving footnotes.
+  pub footnotes: Option<Vec<String>>,
+}
+
+#[napi(object)]
+#[derive(Clone, PartialEq)]
+pub struct AcquiredDisposedFootnotes {
+  #[napi(ts_type = "'A' | 'D'")]
+  pub value: Option<AcquiredDisposed>,
+  pub footnote_ids: Option<Vec<String>>,
+  pub footnotes: Option<Vec<String>>,
+}
+
+#[napi(object)]
+#[derive(Clone, PartialEq)]
+pub struct DirectOrIndirectFootnotes {
+  #[napi(ts_type = "'D' | 'I'")]
+  pub value: Option<DirectOrIndirect>,
+  pub footnote_ids: Option<Vec<String>>,
+  pub footnotes: Option<Vec<String>>,
+}
+
+#[napi(object)]
+#[derive(Clone, PartialEq)]
+pub struct TimelinessFootnotes {
+  #[napi(ts_type = "'E' | 'L'")]
+  pub value: Option<Timeliness>,
+  pub footnote_ids: Option<Vec<String>>,
+  pub footnotes: Option<Vec<String>>,
+}
+
+string_union! {
+  /// Section 16 transaction codes from the Form 4 general instructions.
+  pub enum TransactionCode {
+    OpenMarketPurchase = "P",
+    OpenMarketSale = "S",
+    VoluntaryReport = "V",
+    Grant = "A",
+    DispositionToIssuer = "D",
+    TaxWithholding = "F",
+    Discretionary = "I",
+    ExemptExercise = "M",
+    Conversion = "C",
+    ShortExpiration = "E",
+    LongExpiration = "H",
+    OutOfTheMoneyExercise = "O",
+    InTheMoneyExercise = "X",
+    Gift = "G",
+    SmallAcquisition = "L",
+    Inheritance = "W",
+    VotingTrust = "Z",
+    Other = "J",
+    EquitySwap = "K",
+    TenderDisposition = "U",
+  }
+}
+
+impl TransactionCode {
+  pub fn description(&self) -> &'static str {
+    match self {
+      TransactionCode::OpenMarketPurchase => {
+        "Open market or private purchase of non-derivative or derivative security"
+      }
+      TransactionCode::OpenMarketSale => {
+        "Open market or private sale of non-derivative or derivative security"
+      }
+      TransactionCode::VoluntaryReport => "Transaction voluntarily reported earlier than required",
+      TransactionCode::Grant => "Grant, award or other acquisition pursuant to Rule 16b-3(d)",
+      TransactionCode::DispositionToIssuer => {
+        "Disposition to the issuer of issuer equity securities pursuant to Rule 16b-3(e)"
+      }
+      TransactionCode::TaxWithholding => {
+        "Payment of exercise price or tax liability by delivering or withholding securities \
+         incident to the receipt, exercise or vesting of a security issued in accordance with \
+         Rule 16b-3"
+      }
+      TransactionCode::Discretionary => {
+        "Discretionary transaction in accordance with Rule 16b-3(f) resulting in acquisition or \
+         disposition of issuer securities"
+      }
+      TransactionCode::ExemptExercise => {
+        "Exercise or conversion of derivative security exempted pursuant to Rule 16b-3"
+      }
+      TransactionCode::Conversion => "Conversion of derivative security",
+      TransactionCode::ShortExpiration => "Expiration of short derivative position",
+      TransactionCode::LongExpiration => {
+        "Expiration (or cancellation) of long derivative position with value received"
+      }
+      TransactionCode::OutOfTheMoneyExercise => "Exercise of out-of-the-money derivative security",
+      TransactionCode::InTheMoneyExercise => {
+        "Exercise of in-the-money or at-the-money derivative security"
+      }
+      TransactionCode::Gift => "Bona fide gift",
+      TransactionCode::SmallAcquisition => "Small acquisition under Rule 16a-6",
+      TransactionCode::Inheritance => {
+        "Acquisition or disposition by will or the laws of descent and distribution"
+      }
+      TransactionCode::VotingTrust => "Deposit into or withdrawal from voting trust",
+      TransactionCode::Other => "Other acquisition or disposition",
+      TransactionCode::EquitySwap => {
+        "Transaction in equity swap or instrument with similar characteristics"
+      }
+      TransactionCode::TenderDisposition => {
+        "Disposition pursuant to a tender of shares in a change of control transaction"
+      }
+    }
+  }
+}
+
+string_union! {
+  pub enum AcquiredDisposed {
+    Acquired = "A",
+    Disposed = "D",
+  }
+}
+
+string_union! {
+  pub enum DirectOrIndirect {
+    Direct = "D",
+    Indirect = "I",
+  }
+}
+
+string_union! {
+  /// Whether a transaction was reported earlier or later than required.
+  pub enum Timeliness {
+    Early = "E",
+    Late = "L",
+  }
+}
+
+/// Builds a `<tag><value/><footnoteId/></tag>` field from its parsed parts.
+trait FromValueFootnotes<T> {
+  fn from_value_footnotes(
+    value: Option<T>,
+    footnote_ids: Option<Vec<String>>,
+    footnotes: Option<Vec<String>>,
+  ) -> Self;
+}
+
+macro_rules! impl_from_value_footnotes {
+  ($name:ident, $value:ty) => {
+    impl FromValueFootnotes<$value> for $name {
+      fn from_value_footnotes(
+        value: Option<$value>,
+        footnote_ids: Option<Vec<String>>,
+        footnotes: Option<Vec<String>>,
+      ) -> Self {
+        $name {
+          value,
+          footnote_ids,
+          footnotes,
+        }
+      }
+    }
+  };
+}
+
+impl_from_value_footnotes!(DecimalFootnotes, f64);
+impl_from_value_footnotes!(DateFootnotes, String);
+impl_from_value_footnotes!(StringFootnotes, String);
+impl_from_value_footnotes!(AcquiredDisposedFootnotes, AcquiredDisposed);
+impl_from_value_footnotes!(DirectOrIndirectFootnotes, DirectOrIndirect);
+impl_from_value_footnotes!(TimelinessFootnotes, Timeliness);
+
+/// Any part of a row that may reference footnotes, directly or through nested values.
+pub trait FootnoteRefs {
+  /// Collects the referenced footnote ids in document order.
+  fn footnote_refs<'a>(&'a self, ids: &mut Vec<&'a String>);
+  /// Visits the referenced footnote ids in document order.
+  fn footnote_refs_mut(&mut self, visit: &mut dyn FnMut(&mut String));
+}
+
+macro_rules! impl_footnote_refs {
+  ($($name:ident),*) => {
+    $(
+      impl FootnoteRefs for $name {
+        fn footnote_refs<'a>(&'a self, ids: &mut Vec<&'a String>) {
+          ids.extend(self.footnote_ids.iter().flatten());
+        }
+
+        fn footnote_refs_mut(&mut self, visit: &mut dyn FnMut(&mut String)) {
+          self.footnote_ids.iter_mut().flatten().for_each(visit);
+        }
+      }
+    )*
+  };
+}
+
+impl_footnote_refs!(
+  DecimalFootnotes,
+  DateFootnotes,
+  StringFootnotes,
+  AcquiredDisposedFootnotes,
+  DirectOrIndirectFootnotes,
+  TimelinessFootnotes,
+  TransactionCoding,
+  HoldingCoding
+);
+
+macro_rules! impl_nested_footnote_refs {
+  ($name:ident { $($field:ident),* }) => {
+    impl FootnoteRefs for $name {
+      fn footnote_refs<'a>(&'a self, ids: &mut Vec<&'a String>) {
+        $(self.$field.footnote_refs(ids);)*
+      }
+
+      fn footnote_refs_mut(&mut self, visit: &mut dyn FnMut(&mut String)) {
+        $(self.$field.footnote_refs_mut(visit);)*
+      }
+    }
+  };
+}
+
+impl_nested_footnote_refs!(TransactionAmounts {
+  shares,
+  price_per_share,
+  acquired_disposed_code
+});
+impl_nested_footnote_refs!(DerivativeTransactionAmounts {
+  shares,
+  price_per_share,
+  total_value,
+  acquired_disposed_code
+});
+impl_nested_footnote_refs!(UnderlyingSecurity {
+  title,
+  shares,
+  value
+});
+impl_nested_footnote_refs!(PostTransactionAmounts {
+  shares_owned_following_transaction,
+  value_owned_following_transaction
+});
+impl_nested_footnote_refs!(OwnershipNature {
+  direct_or_indirect_ownership,
+  nature_of_ownership
+});
+impl_nested_footnote_refs!(NonDerivativeTransaction {
+  security_title,
+  transaction_date,
+  deemed_execution_date,
+  transaction_coding,
+  transaction_timeliness,
+  transaction_amounts,
+  post_transaction_amounts,
+  ownership_nature
+});
+impl_nested_footnote_refs!(NonDerivativeHolding {
+  security_title,
+  transaction_coding,
+  post_transaction_amounts,
+  ownership_nature
+});
+impl_nested_footnote_refs!(DerivativeTransaction {
+  security_title,
+  conversion_or_exercise_price,
+  deemed_execution_date,
+  transaction_date,
+  transaction_coding,
+  transaction_timeliness,
+  transaction_amounts,
+  exercise_date,
+  expiration_date,
+  underlying_security,
+  post_transaction_amounts,
+  ownership_nature
+});
+impl_nested_footnote_refs!(DerivativeHolding {
+  security_title,
+  conversion_or_exercise_price,
+  transaction_coding,
+  exercise_date,
+  expiration_date,
+  underlying_security,
+  post_transaction_amounts,
+  ownership_nature
+});
+
+impl<T: FootnoteRefs> FootnoteRefs for Option<T> {
+  fn footnote_refs<'a>(&'a self, ids: &mut Vec<&'a String>) {
+    if let Some(value) = self {
+      value.footnote_refs(ids);
+    }
+  }
+
+  fn footnote_refs_mut(&mut self, visit: &mut dyn FnMut(&mut String)) {
+    if let Some(value) = self {
+      value.footnote_refs_mut(visit);
+    }
+  }
+}
+
+/// The values the four ownership table rows have in common, without their footnotes. Holdings
+/// leave the transaction values unset, and non-derivative rows the derivative terms.
+#[derive(Default)]
+pub struct RowFields {
+  pub security_title: Option<String>,
+  pub transaction_date: Option<String>,
+  pub transaction_code: Option<TransactionCode>,
+  pub shares: Option<f64>,
+  pub price_per_share: Option<f64>,
+  pub acquired_disposed: Option<AcquiredDisposed>,
+  pub shares_owned_following: Option<f64>,
+  pub direct_or_indirect: Option<DirectOrIndirect>,
+  pub nature_of_ownership: Option<String>,
+  pub conversion_or_exercise_price: Option<f64>,
+  pub expiration_date: Option<String>,
+}
+
+impl RowFields {
+  /// The signed share change of a transaction, negative for dispositions.
+  pub fn share_change(&self) -> Option<f64> {
+    match self.acquired_disposed? {
+      AcquiredDisposed::Acquired => self.shares,
+      AcquiredDisposed::Disposed => self.shares.map(|shares| -shares),
+    }
+  }
+}
+
+/// A row of one of the four ownership tables.
+pub trait TableRow {
+  fn row_fields(&self) -> RowFields;
+}
+
+/// Non-derivative and derivative transactions share these element names.
+macro_rules! impl_transaction_fields {
+  ($($name:ident),*) => {
+    $(
+      impl $name {
+        fn transaction_fields(&self) -> RowFields {
+          let amounts = self.transaction_amounts.as_ref();
+          RowFields {
+            transaction_date: self
+              .transaction_date
+              .as_ref()
+              .and_then(|date| date.value.clone()),
+            transaction_code: self
+              .transaction_coding
+              .as_ref()
+              .and_then(|coding| coding.transaction_code),
+            shares: amounts
+              .and_then(|amounts| amounts.shares.as_ref())
+              .and_then(|shares| shares.value),
+            price_per_share: amounts
+              .and_then(|amounts| amounts.price_per_share.as_ref())
+              .and_then(|price| price.value),
+            acquired_disposed: amounts
+              .and_then(|amounts| amounts.acquired_disposed_code.as_ref())
+              .and_then(|code| code.value),
+            ..holding_fields(
+              &self.security_title,
+              &self.post_transaction_amounts,
+              &self.ownership_nature,
+            )
+          }
+        }
+      }
+    )*
+  };
+}
+
+impl_transaction_fields!(NonDerivativeTransaction, DerivativeTransaction);
+
+impl TableRow for NonDerivativeTransaction {
+  fn row_fields(&self) -> RowFields {
+    self.transaction_fields()
+  }
+}
+
+impl TableRow for DerivativeTransaction {
+  fn row_fields(&self) -> RowFields {
+    RowFields {
+      conversion_or_exercise_price: decimal_value(&self.conversion_or_exercise_price),
+      expiration_date: date_value(&self.expiration_date),
+      ..self.transaction_fields()
+    }
+  }
+}
+
+impl TableRow for NonDerivativeHolding {
+  fn row_fields(&self) -> RowFields {
+    holding_fields(
+      &self.security_title,
+      &self.post_transaction_amounts,
+      &self.ownership_nature,
+    )
+  }
+}
+
+impl TableRow for DerivativeHolding {
+  fn row_fields(&self) -> RowFields {
+    RowFields {
+      conversion_or_exercise_price: decimal_value(&self.conversion_or_exercise_price),
+      expiration_date: date_value(&self.expiration_date),
+      ..holding_fields(
+        &self.security_title,
+        &self.post_transaction_amounts,
+        &self.ownership_nature,
+      )
+    }
+  }
+}
+
+fn decimal_value(decimal: &Option<DecimalFootnotes>) -> Option<f64> {
+  decimal.as_ref().and_then(|decimal| decimal.value)
+}
+
+fn date_value(date: &Option<DateFootnotes>) -> Option<String> {
+  date.as_ref().and_then(|date| date.value.clone())
+}
+
+fn holding_fields(
+  security_title: &Option<StringFootnotes>,
+  post_transaction_amounts: &Option<PostTransactionAmounts>,
+  ownership_nature: &Option<OwnershipNature>,
+) -> RowFields {
+  let nature = ownership_nature.as_ref();
+  RowFields {
+    security_title: security_title
+      .as_ref()
+      .and_then(|title| title.value.clone()),
+    shares_owned_following: post_transaction_amounts
+      .as_ref()
+      .and_then(|amounts| amounts.shares_owned_following_transaction.as_ref())
+      .and_then(|shares| shares.value),
+    direct_or_indirect: nature
+      .and_then(|nature| nature.direct_or_indirect_ownership.as_ref())
+      .and_then(|ownership| ownership.value),
+    nature_of_ownership: nature
+      .and_then(|nature| nature.nature_of_ownership.as_ref())
+      .and_then(|nature| nature.value.clone()),
+    ..Default::default()
+  }
 }
 
+impl OwnershipForm {
+  pub fn is_amendment(&self) -> bool {
+    self.date_of_original_submission.is_some() || self.document_type.ends_with("/A")
+  }
+
+  /// Every transaction and holding with its table and index within the table, non-derivative
+  /// rows first, in filing order.
+  pub fn table_rows(&self) -> Vec<(OwnershipTableKind, usize, RowFields)> {
+    fn push<T: TableRow>(
+      rows: &mut Vec<(OwnershipTableKind, usize, RowFields)>,
+      table: OwnershipTableKind,
+      table_rows: &[T],
+    ) {
+      rows.extend(
+        table_rows
+          .iter()
+          .enumerate()
+          .map(|(index, row)| (table, index, row.row_fields())),
+      );
+    }
+
+    let mut rows = vec![];
+    if let Some(table) = &self.non_derivative_table {
+      push(
+        &mut rows,
+        OwnershipTableKind::NonDerivativeTransaction,
+        &table.transactions,
+      );
+      push(
+        &mut rows,
+        OwnershipTableKind::NonDerivativeHolding,
+        &table.holdings,
+      );
+    }
+    if let Some(table) = &self.derivative_table {
+      push(
+        &mut rows,
+        OwnershipTableKind::DerivativeTransaction,
+        &table.transactions,
+      );
+      push(
+        &mut rows,
+        OwnershipTableKind::DerivativeHolding,
+        &table.holdings,
+      );
+    }
+    rows
+  }
+}
+
+/// Per-document state shared by the value parsers.
+struct ValueContext {
+  notes: Option<HashMap<String, String>>,
+}
+
+impl ValueContext {
+  fn new(footnotes: &[Footnote], resolve_footnotes: bool) -> Self {
+    let notes = resolve_footnotes.then(|| {
+      footnotes
+        .iter()
+        .filter_map(|footnote| {
+          let id = footnote.id.clone()?;
+          Some((id, footnote.note.clone().unwrap_or_default()))
+        })
+        .collect()
+    });
+    ValueContext { notes }
+  }
+
+  fn resolve(&self, footnote_ids: &Option<Vec<String>>) -> Option<Vec<String>> {
+    let notes = self.notes.as_ref()?;
+    footnote_ids
+      .as_ref()
+      .map(|ids| ids.iter().filter_map(|id| notes.get(id).cloned()).collect())
+  }
+}
+
+const OWNERSHIP_DOCUMENT_ELEMENTS: &[&str] = &[
+  "schemaVersion",
+  "documentType",
+  "periodOfReport",
+  "dateOfOriginalSubmission",
+  "noSecuritiesOwned",
+  "notSubjectToSection16",
+  "form3HoldingsReported",
+  "form4TransactionsReported",
+  "issuer",
+  "reportingOwner",
+  "aff10b5One",
+  "nonDerivativeTable",
+  "derivativeTable",
+  "footnotes",
+  "remarks",
+  "ownerSignature",
+];
+
+const ISSUER_ELEMENTS: &[&str] = &["issuerCik", "issuerName", "issuerTradingSymbol"];
+
+const REPORTING_OWNER_ELEMENTS: &[&str] = &[
+  "reportingOwnerId",
+  "reportingOwnerAddress",
+  "reportingOwnerRelationship",
+];
+
+const REPORTING_OWNER_ID_ELEMENTS: &[&str] = &["rptOwnerCik", "rptOwnerCcc", "rptOwnerName"];
+
+const REPORTING_OWNER_ADDRESS_ELEMENTS: &[&str] = &[
+  "rptOwnerStreet1",
+  "rptOwnerStreet2",
+  "rptOwnerCity",
+  "rptOwnerState",
+  "rptOwnerZipCode",
+  "rptOwnerStateDescription",
+];
+
+const REPORTING_OWNER_RELATIONSHIP_ELEMENTS: &[&str] = &[
+  "isDirector",
+  "isOfficer",
+  "isTenPercentOwner",
+  "isOther",
+  "officerTitle",
+  "otherText",
+];
+
+const NON_DERIVATIVE_TABLE_ELEMENTS: &[&str] =
+  &["nonDerivativeTransaction", "nonDerivativeHolding"];
+
+const DERIVATIVE_TABLE_ELEMENTS: &[&str] = &["derivativeTransaction", "derivativeHolding"];
+
+const NON_DERIVATIVE_TRANSACTION_ELEMENTS: &[&str] = &[
+  "securityTitle",
+  "transactionDate",
+  "deemedExecutionDate",
+  "transactionCoding",
+  "transactionTimeliness",
+  "transactionAmounts",
+  "postTransactionAmounts",
+  "ownershipNature",
+];
+
+const DERIVATIVE_TRANSACTION_ELEMENTS: &[&str] = &[
+  "securityTitle",
+  "conversionOrExercisePrice",
+  "transactionDate",
+  "deemedExecutionDate",
+  "transactionCoding",
+  "transactionTimeliness",
+  "transactionAmounts",
+  "exerciseDate",
+  "expirationDate",
+  "underlyingSecurity",
+  "postTransactionAmounts",
+  "ownershipNature",
+];
+
+const NON_DERIVATIVE_HOLDING_ELEMENTS: &[&str] = &[
+  "securityTitle",
+  "transactionCoding",
+  "postTransactionAmounts",
+  "ownershipNature",
+];
+
+const DERIVATIVE_HOLDING_ELEMENTS: &[&str] = &[
+  "securityTitle",
+  "conversionOrExercisePrice",
+  "transactionCoding",
+  "exerciseDate",
+  "expirationDate",
+  "underlyingSecurity",
+  "postTransactionAmounts",
+  "ownershipNature",
+];
+
+const TRANSACTION_CODING_ELEMENTS: &[&str] = &[
+  "transactionFormType",
+  "transactionCode",
+  "equitySwapInvolved",
+  "footnoteId",
+];
+
+const HOLDING_CODING_ELEMENTS: &[&str] = &["transactionFormType", "footnoteId"];
+
+const TRANSACTION_AMOUNTS_ELEMENTS: &[&str] = &[
+  "transactionShares",
+  "transactionPricePerShare",
+  "transactionAcquiredDisposedCode",
+];
+
+const DERIVATIVE_TRANSACTION_AMOUNTS_ELEMENTS: &[&str] = &[
+  "transactionShares",
+  "transactionTotalValue",
+  "transactionPricePerShare",
+  "transactionAcquiredDisposedCode",
+];
+
+const UNDERLYING_SECURITY_ELEMENTS: &[&str] = &[
+  "underlyingSecurityTitle",
+  "underlyingSecurityShares",
+  "underlyingSecurityValue",
+];
+
+const POST_TRANSACTION_AMOUNTS_ELEMENTS: &[&str] = &[
+  "sharesOwnedFollowingTransaction",
+  "valueOwnedFollowingTransaction",
+];
+
+const OWNERSHIP_NATURE_ELEMENTS: &[&str] = &["directOrIndirectOwnership", "natureOfOwnership"];
+
+/// Returns the Form 4 description of a Section 16 transaction code such as `P` or `S`.
 #[napi]
-pub fn parse_ownership_form(env: Env, form: String) -> Result<OwnershipForm, Error> {
-  let doc = XMLDoc::parse(&form).map_err(|e| Error::from_reason(e.to_string()))?;
-  let root_node = doc.root_element();
-  let schema_version = parse_string::<String>(&root_node, "schemaVersion");
-  let document_type = parse_string::<String>(&root_node, "documentType")
-    .ok_or("documentType not found".to_string())
-    .map_err(Error::from_reason)?;
-  let period_of_report = parse_string::<String>(&root_node, "periodOfReport")
-    .ok_or("periodOfReport not found".to_string())
-    .map_err(Error::from_reason)?;
-  let date_of_original_submission = parse_string::<String>(&root_node, "dateOfOriginalSubmission");
-  let no_securities_owned = parse_string::<bool>(&root_node, "noSecuritiesOwned");
-  let not_subject_to_section_16 = parse_string::<bool>(&root_node, "notSubjectToSection16");
-  let form3_holdings_reported = parse_string::<bool>(&root_node, "form3HoldingsReported");
-  let form4_transactions_reported = parse_string::<bool>(&root_node, "form4TransactionsReported");
-  let aff10b5_one = parse_string::<bool>(&root_node, "aff10b5One");
-  let issuer = parse_issuer(&root_node).map_err(Error::from_reason)?;
-  let reporting_owners = parse_reporting_owners(&root_node).map_err(Error::from_reason)?;
-  let non_derivative_table =
-    parse_non_derivative_table(env, &root_node).map_err(Error::from_reason)?;
-  let derivative_table = parse_derivative_table(env, &root_node).map_err(Error::from_reason)?;
-  let footnotes = parse_footnotes(&root_node).map_err(Error::from_reason)?;
-  let remarks = parse_string::<String>(&root_node, "remarks");
-  let owner_signatures = parse_owner_signatures(&root_node).map_err(Error::from_reason)?;
+pub fn describe_transaction_code(code: String) -> Option<String> {
+  code
+    .parse::<TransactionCode>()
+    .ok()
+    .map(|code| code.description().to_owned())
+}
+
+#[napi]
+pub fn parse_ownership_form(
+  env: Env,
+  form: String,
+  options: Option<ParseOptions>,
+) -> Result<OwnershipForm, Error> {
+  let doc = XMLDoc::parse(&form).map_err(|e| ParseError::from(e).into_napi(env))?;
+  let diagnostics = &Diagnostics::new(ParseOptions::mode(&options));
+  let resolve_footnotes = ParseOptions::resolve_footnotes(&options);
+  parse_ownership_document(&doc.root_element(), resolve_footnotes, diagnostics)
+    .map_err(|e| e.into_napi(env))
+}
+
+fn parse_ownership_document(
+  root_node: &Node,
+  resolve_footnotes: bool,
+  diagnostics: &Diagnostics,
+) -> Result<OwnershipForm, ParseError> {
+  diagnostics.unknown_elements(root_node, OWNERSHIP_DOCUMENT_ELEMENTS)?;
+  let schema_version = parse_string::<String>(root_node, "schemaVersion");
+  let document_type = parse_field::<String>(root_node, "documentType", diagnostics)?;
+  let period_of_report = parse_field::<String>(root_node, "periodOfReport", diagnostics)?;
+  let date_of_original_submission = parse_string::<String>(root_node, "dateOfOriginalSubmission");
+  let no_securities_owned = parse_optional::<bool>(root_node, "noSecuritiesOwned", diagnostics)?;
+  let not_subject_to_section_16 =
+    parse_optional::<bool>(root_node, "notSubjectToSection16", diagnostics)?;
+  let form3_holdings_reported =
+    parse_optional::<bool>(root_node, "form3HoldingsReported", diagnostics)?;
+  let form4_transactions_reported =
+    parse_optional::<bool>(root_node, "form4TransactionsReported", diagnostics)?;
+  let aff10b5_one = parse_optional::<bool>(root_node, "aff10b5One", diagnostics)?;
+  let issuer = parse_issuer(root_node, diagnostics)?;
+  let reporting_owners = parse_reporting_owners(root_node, diagnostics)?;
+  let footnotes = parse_footnotes(root_node, resolve_footnotes, diagnostics)?;
+  let values = &ValueContext::new(&footnotes, resolve_footnotes);
+  let non_derivative_table = parse_non_derivative_table(values, root_node, diagnostics)?;
+  let derivative_table = parse_derivative_table(values, root_node, diagnostics)?;
+  let remarks = parse_string::<String>(root_node, "remarks");
+  let owner_signatures = parse_owner_signatures(root_node, diagnostics)?;
 
   Ok(OwnershipForm {
     schema_version,
@@ -236,73 +963,72 @@ pub fn parse_ownership_form(env: Env, form: String) -> Result<OwnershipForm, Err
     footnotes,
     remarks,
     owner_signatures,
+    warnings: diagnostics.take_warnings(),
   })
 }
 
-fn parse_issuer(node: &Node) -> Result<Issuer, String> {
-  node
-    .children()
-    .find(|node| node.has_tag_name("issuer"))
-    .ok_or("issuer not found".to_string())
-    .and_then(|issuer_node| {
-      let cik = parse_string::<String>(&issuer_node, "issuerCik")
-        .ok_or("issuerCik not found".to_string())?;
-      let name = parse_string::<String>(&issuer_node, "issuerName");
-      let trading_symbol = parse_string::<String>(&issuer_node, "issuerTradingSymbol")
-        .ok_or("issuerTradingSymbol not found".to_string())?;
-
-      Ok(Issuer {
-        cik,
-        name,
-        trading_symbol,
-      })
+fn parse_issuer(node: &Node, diagnostics: &Diagnostics) -> Result<Issuer, ParseError> {
+  find_required(node, "issuer").and_then(|issuer_node| {
+    diagnostics.unknown_elements(&issuer_node, ISSUER_ELEMENTS)?;
+    let cik = parse_field::<String>(&issuer_node, "issuerCik", diagnostics)?;
+    let name = parse_string::<String>(&issuer_node, "issuerName");
+    let trading_symbol = parse_field::<String>(&issuer_node, "issuerTradingSymbol", diagnostics)?;
+
+    Ok(Issuer {
+      cik,
+      name,
+      trading_symbol,
     })
+  })
 }
 
-fn parse_reporting_owners(node: &Node) -> Result<Vec<ReportingOwner>, String> {
-  let owners = node
-    .children()
-    .filter(|node| node.has_tag_name("reportingOwner"))
-    .map(|owner_node| {
-      let id = parse_reporting_owner_id(&owner_node)?;
-      let address = parse_reporting_owner_address(&owner_node)?;
-      let relationship = parse_reporting_owner_relationship(&owner_node)?;
-
-      Ok(ReportingOwner {
-        id,
-        address,
-        relationship,
-      })
+fn parse_reporting_owners(
+  node: &Node,
+  diagnostics: &Diagnostics,
+) -> Result<Vec<ReportingOwner>, ParseError> {
+  let owners = parse_rows(node, "reportingOwner", diagnostics, |owner_node| {
+    diagnostics.unknown_elements(owner_node, REPORTING_OWNER_ELEMENTS)?;
+    let id = parse_reporting_owner_id(owner_node, diagnostics)?;
+    let address = parse_reporting_owner_address(owner_node, diagnostics)?;
+    let relationship = parse_reporting_owner_relationship(owner_node, diagnostics)?;
+
+    Ok(ReportingOwner {
+      id,
+      address,
+      relationship,
     })
-    .collect::<Result<Vec<ReportingOwner>, String>>();
+  })?;
 
-  if owners.as_ref().map_or(true, |v| v.is_empty()) {
-    Err("reportingOwner not found".to_string())
+  if owners.is_empty() {
+    Err(ParseError::missing(node, "reportingOwner"))
   } else {
-    owners
+    Ok(owners)
   }
 }
 
-fn parse_reporting_owner_id(node: &Node) -> Result<ReportingOwnerID, String> {
-  node
-    .children()
-    .find(|node| node.has_tag_name("reportingOwnerId"))
-    .ok_or("reportingOwnerId not found".to_string())
-    .and_then(|id_node| {
-      let cik = parse_string::<String>(&id_node, "rptOwnerCik")
-        .ok_or("rptOwnerCik not found".to_string())?;
-      let ccc = parse_string::<String>(&id_node, "rptOwnerCcc");
-      let name = parse_string::<String>(&id_node, "rptOwnerName");
-
-      Ok(ReportingOwnerID { cik, ccc, name })
-    })
+fn parse_reporting_owner_id(
+  node: &Node,
+  diagnostics: &Diagnostics,
+) -> Result<ReportingOwnerID, ParseError> {
+  find_required(node, "reportingOwnerId").and_then(|id_node| {
+    diagnostics.unknown_elements(&id_node, REPORTING_OWNER_ID_ELEMENTS)?;
+    let cik = parse_required::<String>(&id_node, "rptOwnerCik")?;
+    let ccc = parse_string::<String>(&id_node, "rptOwnerCcc");
+    let name = parse_string::<String>(&id_node, "rptOwnerName");
+
+    Ok(ReportingOwnerID { cik, ccc, name })
+  })
 }
 
-fn parse_reporting_owner_address(node: &Node) -> Result<Option<ReportingOwnerAddress>, String> {
+fn parse_reporting_owner_address(
+  node: &Node,
+  diagnostics: &Diagnostics,
+) -> Result<Option<ReportingOwnerAddress>, ParseError> {
   node
     .children()
     .find(|node| node.has_tag_name("reportingOwnerAddress"))
     .map(|address_node| {
+      diagnostics.unknown_elements(&address_node, REPORTING_OWNER_ADDRESS_ELEMENTS)?;
       let street1 = parse_string::<String>(&address_node, "rptOwnerStreet1");
       let street2 = parse_string::<String>(&address_node, "rptOwnerStreet2");
       let city = parse_string::<String>(&address_node, "rptOwnerCity");
@@ -324,15 +1050,18 @@ fn parse_reporting_owner_address(node: &Node) -> Result<Option<ReportingOwnerAdd
 
 fn parse_reporting_owner_relationship(
   node: &Node,
-) -> Result<Option<ReportingOwnerRelationship>, String> {
+  diagnostics: &Diagnostics,
+) -> Result<Option<ReportingOwnerRelationship>, ParseError> {
   node
     .children()
     .find(|node| node.has_tag_name("reportingOwnerRelationship"))
     .map(|relationship_node| {
-      let is_director = parse_string::<bool>(&relationship_node, "isDirector");
-      let is_officer = parse_string::<bool>(&relationship_node, "isOfficer");
-      let is_ten_percent_owner = parse_string::<bool>(&relationship_node, "isTenPercentOwner");
-      let is_other = parse_string::<bool>(&relationship_node, "isOther");
+      diagnostics.unknown_elements(&relationship_node, REPORTING_OWNER_RELATIONSHIP_ELEMENTS)?;
+      let is_director = parse_optional::<bool>(&relationship_node, "isDirector", diagnostics)?;
+      let is_officer = parse_optional::<bool>(&relationship_node, "isOfficer", diagnostics)?;
+      let is_ten_percent_owner =
+        parse_optional::<bool>(&relationship_node, "isTenPercentOwner", diagnostics)?;
+      let is_other = parse_optional::<bool>(&relationship_node, "isOther", diagnostics)?;
       let officer_title = parse_string::<String>(&relationship_node, "officerTitle");
       let other_text = parse_string::<String>(&relationship_node, "otherText");
 
@@ -348,13 +1077,18 @@ fn parse_reporting_owner_relationship(
     .transpose()
 }
 
-fn parse_non_derivative_table(env: Env, node: &Node) -> Result<Option<NonDerivativeTable>, String> {
+fn parse_non_derivative_table(
+  values: &ValueContext,
+  node: &Node,
+  diagnostics: &Diagnostics,
+) -> Result<Option<NonDerivativeTable>, ParseError> {
   node
     .children()
     .find(|node| node.has_tag_name("nonDerivativeTable"))
     .map(|table_node| {
-      let transactions = parse_non_derivative_transactions(env, &table_node)?;
-      let holdings = parse_non_derivative_holdings(env, &table_node)?;
+      diagnostics.unknown_elements(&table_node, NON_DERIVATIVE_TABLE_ELEMENTS)?;
+      let transactions = parse_non_derivative_transactions(values, &table_node, diagnostics)?;
+      let holdings = parse_non_derivative_holdings(values, &table_node, diagnostics)?;
 
       Ok(NonDerivativeTable {
         transactions,
@@ -364,13 +1098,18 @@ fn parse_non_derivative_table(env: Env, node: &Node) -> Result<Option<NonDerivat
     .transpose()
 }
 
-fn parse_derivative_table(env: Env, node: &Node) -> Result<Option<DerivativeTable>, String> {
+fn parse_derivative_table(
+  values: &ValueContext,
+  node: &Node,
+  diagnostics: &Diagnostics,
+) -> Result<Option<DerivativeTable>, ParseError> {
   node
     .children()
     .find(|node| node.has_tag_name("derivativeTable"))
     .map(|table_node| {
-      let transactions = parse_derivative_transactions(env, &table_node)?;
-      let holdings = parse_derivative_holdings(env, &table_node)?;
+      diagnostics.unknown_elements(&table_node, DERIVATIVE_TABLE_ELEMENTS)?;
+      let transactions = parse_derivative_transactions(values, &table_node, diagnostics)?;
+      let holdings = parse_derivative_holdings(values, &table_node, diagnostics)?;
 
       Ok(DerivativeTable {
         transactions,
@@ -381,25 +1120,43 @@ fn parse_derivative_table(env: Env, node: &Node) -> Result<Option<DerivativeTabl
 }
 
 fn parse_non_derivative_transactions(
-  env: Env,
+  values: &ValueContext,
   node: &Node,
-) -> Result<Vec<NonDerivativeTransaction>, String> {
-  let transactions = node
-    .children()
-    .filter(|node| node.has_tag_name("nonDerivativeTransaction"))
-    .filter_map(|transaction_node| {
-      let security_title = get_value_footnotes(env, &transaction_node, "securityTitle");
-      let transaction_date = get_value_footnotes(env, &transaction_node, "transactionDate");
-      let deemed_execution_date =
-        get_value_footnotes(env, &transaction_node, "deemedExecutionDate");
-      let transaction_timeliness =
-        get_value_footnotes(env, &transaction_node, "transactionTimeliness");
-      let transaction_coding = parse_transaction_coding(&transaction_node).ok()?;
-      let transaction_amounts = parse_transaction_amounts(env, &transaction_node).ok()?;
-      let post_transaction_amounts = parse_post_transaction_amounts(env, &transaction_node).ok()?;
-      let ownership_nature = parse_ownership_nature(env, &transaction_node).ok()?;
-
-      Some(NonDerivativeTransaction {
+  diagnostics: &Diagnostics,
+) -> Result<Vec<NonDerivativeTransaction>, ParseError> {
+  parse_rows(
+    node,
+    "nonDerivativeTransaction",
+    diagnostics,
+    |transaction_node| {
+      diagnostics.unknown_elements(transaction_node, NON_DERIVATIVE_TRANSACTION_ELEMENTS)?;
+      let security_title =
+        get_value_footnotes::<String, _>(values, transaction_node, "securityTitle", diagnostics)?;
+      let transaction_date = get_value_footnotes::<IsoDate, _>(
+        values,
+        transaction_node,
+        "transactionDate",
+        diagnostics,
+      )?;
+      let deemed_execution_date = get_value_footnotes::<IsoDate, _>(
+        values,
+        transaction_node,
+        "deemedExecutionDate",
+        diagnostics,
+      )?;
+      let transaction_timeliness = get_value_footnotes::<Timeliness, _>(
+        values,
+        transaction_node,
+        "transactionTimeliness",
+        diagnostics,
+      )?;
+      let transaction_coding = parse_transaction_coding(values, transaction_node, diagnostics)?;
+      let transaction_amounts = parse_transaction_amounts(values, transaction_node, diagnostics)?;
+      let post_transaction_amounts =
+        parse_post_transaction_amounts(values, transaction_node, diagnostics)?;
+      let ownership_nature = parse_ownership_nature(values, transaction_node, diagnostics)?;
+
+      Ok(NonDerivativeTransaction {
         security_title,
         transaction_date,
         deemed_execution_date,
@@ -409,38 +1166,60 @@ fn parse_non_derivative_transactions(
         post_transaction_amounts,
         ownership_nature,
       })
-    })
-    .collect();
-
-  Ok(transactions)
+    },
+  )
 }
 
 fn parse_derivative_transactions(
-  env: Env,
+  values: &ValueContext,
   node: &Node,
-) -> Result<Vec<DerivativeTransaction>, String> {
-  let transactions = node
-    .children()
-    .filter(|node| node.has_tag_name("derivativeTransaction"))
-    .filter_map(|transaction_node| {
-      let security_title = get_value_footnotes(env, &transaction_node, "securityTitle");
-      let conversion_or_exercise_price =
-        get_value_footnotes(env, &transaction_node, "conversionOrExercisePrice");
-      let transaction_date = get_value_footnotes(env, &transaction_node, "transactionDate");
-      let deemed_execution_date =
-        get_value_footnotes(env, &transaction_node, "deemedExecutionDate");
-      let transaction_coding = parse_transaction_coding(&transaction_node).ok()?;
-      let transaction_timeliness =
-        get_value_footnotes(env, &transaction_node, "transactionTimeliness");
+  diagnostics: &Diagnostics,
+) -> Result<Vec<DerivativeTransaction>, ParseError> {
+  parse_rows(
+    node,
+    "derivativeTransaction",
+    diagnostics,
+    |transaction_node| {
+      diagnostics.unknown_elements(transaction_node, DERIVATIVE_TRANSACTION_ELEMENTS)?;
+      let security_title =
+        get_value_footnotes::<String, _>(values, transaction_node, "securityTitle", diagnostics)?;
+      let conversion_or_exercise_price = get_value_footnotes::<f64, _>(
+        values,
+        transaction_node,
+        "conversionOrExercisePrice",
+        diagnostics,
+      )?;
+      let transaction_date = get_value_footnotes::<IsoDate, _>(
+        values,
+        transaction_node,
+        "transactionDate",
+        diagnostics,
+      )?;
+      let deemed_execution_date = get_value_footnotes::<IsoDate, _>(
+        values,
+        transaction_node,
+        "deemedExecutionDate",
+        diagnostics,
+      )?;
+      let transaction_coding = parse_transaction_coding(values, transaction_node, diagnostics)?;
+      let transaction_timeliness = get_value_footnotes::<Timeliness, _>(
+        values,
+        transaction_node,
+        "transactionTimeliness",
+        diagnostics,
+      )?;
       let transaction_amounts =
-        parse_derivative_transaction_amounts(env, &transaction_node).ok()?;
-      let exercise_date = get_value_footnotes(env, &transaction_node, "exerciseDate");
-      let expiration_date = get_value_footnotes(env, &transaction_node, "expirationDate");
-      let underlying_security = parse_underlying_security(env, &transaction_node).ok()?;
-      let post_transaction_amounts = parse_post_transaction_amounts(env, &transaction_node).ok()?;
-      let ownership_nature = parse_ownership_nature(env, &transaction_node).ok()?;
-
-      Some(DerivativeTransaction {
+        parse_derivative_transaction_amounts(values, transaction_node, diagnostics)?;
+      let exercise_date =
+        get_value_footnotes::<IsoDate, _>(values, transaction_node, "exerciseDate", diagnostics)?;
+      let expiration_date =
+        get_value_footnotes::<IsoDate, _>(values, transaction_node, "expirationDate", diagnostics)?;
+      let underlying_security = parse_underlying_security(values, transaction_node, diagnostics)?;
+      let post_transaction_amounts =
+        parse_post_transaction_amounts(values, transaction_node, diagnostics)?;
+      let ownership_nature = parse_ownership_nature(values, transaction_node, diagnostics)?;
+
+      Ok(DerivativeTransaction {
         security_title,
         conversion_or_exercise_price,
         deemed_execution_date,
@@ -454,113 +1233,147 @@ fn parse_derivative_transactions(
         post_transaction_amounts,
         ownership_nature,
       })
-    })
-    .collect();
-
-  Ok(transactions)
+    },
+  )
 }
 
 fn parse_non_derivative_holdings(
-  env: Env,
+  values: &ValueContext,
   node: &Node,
-) -> Result<Vec<NonDerivativeHolding>, String> {
-  let holdings = node
-    .children()
-    .filter(|node| node.has_tag_name("nonDerivativeHolding"))
-    .filter_map(|holdings_node| {
-      let security_title = get_value_footnotes(env, &holdings_node, "securityTitle");
-      let transaction_coding = parse_holding_coding(&holdings_node).ok()?;
-      let post_transaction_amounts = parse_post_transaction_amounts(env, &holdings_node).ok()?;
-      let ownership_nature = parse_ownership_nature(env, &holdings_node).ok()?;
-
-      Some(NonDerivativeHolding {
-        security_title,
-        transaction_coding,
-        post_transaction_amounts,
-        ownership_nature,
-      })
+  diagnostics: &Diagnostics,
+) -> Result<Vec<NonDerivativeHolding>, ParseError> {
+  parse_rows(node, "nonDerivativeHolding", diagnostics, |holdings_node| {
+    diagnostics.unknown_elements(holdings_node, NON_DERIVATIVE_HOLDING_ELEMENTS)?;
+    let security_title =
+      get_value_footnotes::<String, _>(values, holdings_node, "securityTitle", diagnostics)?;
+    let transaction_coding = parse_holding_coding(values, holdings_node, diagnostics)?;
+    let post_transaction_amounts =
+      parse_post_transaction_amounts(values, holdings_node, diagnostics)?;
+    let ownership_nature = parse_ownership_nature(values, holdings_node, diagnostics)?;
+
+    Ok(NonDerivativeHolding {
+      security_title,
+      transaction_coding,
+      post_transaction_amounts,
+      ownership_nature,
     })
-    .collect();
-
-  Ok(holdings)
+  })
 }
 
-fn parse_derivative_holdings(env: Env, node: &Node) -> Result<Vec<DerivativeHolding>, String> {
-  let holdings = node
-    .children()
-    .filter(|node| node.has_tag_name("derivativeHolding"))
-    .filter_map(|holdings_node| {
-      let security_title = get_value_footnotes(env, &holdings_node, "securityTitle");
-      let conversion_or_exercise_price =
-        get_value_footnotes(env, &holdings_node, "conversionOrExercisePrice");
-      let transaction_coding = parse_holding_coding(&holdings_node).ok()?;
-      let exercise_date = get_value_footnotes(env, &holdings_node, "exerciseDate");
-      let expiration_date = get_value_footnotes(env, &holdings_node, "expirationDate");
-      let underlying_security = parse_underlying_security(env, &holdings_node).ok()?;
-      let post_transaction_amounts = parse_post_transaction_amounts(env, &holdings_node).ok()?;
-      let ownership_nature = parse_ownership_nature(env, &holdings_node).ok()?;
-
-      Some(DerivativeHolding {
-        security_title,
-        conversion_or_exercise_price,
-        transaction_coding,
-        exercise_date,
-        expiration_date,
-        underlying_security,
-        post_transaction_amounts,
-        ownership_nature,
-      })
+fn parse_derivative_holdings(
+  values: &ValueContext,
+  node: &Node,
+  diagnostics: &Diagnostics,
+) -> Result<Vec<DerivativeHolding>, ParseError> {
+  parse_rows(node, "derivativeHolding", diagnostics, |holdings_node| {
+    diagnostics.unknown_elements(holdings_node, DERIVATIVE_HOLDING_ELEMENTS)?;
+    let security_title =
+      get_value_footnotes::<String, _>(values, holdings_node, "securityTitle", diagnostics)?;
+    let conversion_or_exercise_price = get_value_footnotes::<f64, _>(
+      values,
+      holdings_node,
+      "conversionOrExercisePrice",
+      diagnostics,
+    )?;
+    let transaction_coding = parse_holding_coding(values, holdings_node, diagnostics)?;
+    let exercise_date =
+      get_value_footnotes::<IsoDate, _>(values, holdings_node, "exerciseDate", diagnostics)?;
+    let expiration_date =
+      get_value_footnotes::<IsoDate, _>(values, holdings_node, "expirationDate", diagnostics)?;
+    let underlying_security = parse_underlying_security(values, holdings_node, diagnostics)?;
+    let post_transaction_amounts =
+      parse_post_transaction_amounts(values, holdings_node, diagnostics)?;
+    let ownership_nature = parse_ownership_nature(values, holdings_node, diagnostics)?;
+
+    Ok(DerivativeHolding {
+      security_title,
+      conversion_or_exercise_price,
+      transaction_coding,
+      exercise_date,
+      expiration_date,
+      underlying_security,
+      post_transaction_amounts,
+      ownership_nature,
     })
-    .collect();
-
-  Ok(holdings)
+  })
 }
 
-fn parse_transaction_coding(node: &Node) -> Result<Option<TransactionCoding>, String> {
+fn parse_transaction_coding(
+  values: &ValueContext,
+  node: &Node,
+  diagnostics: &Diagnostics,
+) -> Result<Option<TransactionCoding>, ParseError> {
   node
     .children()
     .find(|node| node.has_tag_name("transactionCoding"))
     .map(|coding_node| {
+      diagnostics.unknown_elements(&coding_node, TRANSACTION_CODING_ELEMENTS)?;
       let form_type = parse_string::<String>(&coding_node, "transactionFormType");
-      let transaction_code = parse_string::<String>(&coding_node, "transactionCode");
-      let equity_swap_involved = parse_string::<bool>(&coding_node, "equitySwapInvolved");
+      let transaction_code =
+        parse_optional::<TransactionCode>(&coding_node, "transactionCode", diagnostics)?;
+      let equity_swap_involved =
+        parse_optional::<bool>(&coding_node, "equitySwapInvolved", diagnostics)?;
       let footnote_ids = parse_footnote_ids(&coding_node);
+      let footnotes = values.resolve(&footnote_ids);
 
       Ok(TransactionCoding {
         form_type,
         transaction_code,
         equity_swap_involved,
         footnote_ids,
+        footnotes,
       })
     })
     .transpose()
 }
 
-fn parse_holding_coding(node: &Node) -> Result<Option<HoldingCoding>, String> {
+fn parse_holding_coding(
+  values: &ValueContext,
+  node: &Node,
+  diagnostics: &Diagnostics,
+) -> Result<Option<HoldingCoding>, ParseError> {
   node
     .children()
     .find(|node| node.has_tag_name("transactionCoding"))
     .map(|coding_node| {
+      diagnostics.unknown_elements(&coding_node, HOLDING_CODING_ELEMENTS)?;
       let form_type = parse_string::<String>(&coding_node, "transactionFormType");
       let footnote_ids = parse_footnote_ids(&coding_node);
+      let footnotes = values.resolve(&footnote_ids);
 
       Ok(HoldingCoding {
         form_type,
         footnote_ids,
+        footnotes,
       })
     })
     .transpose()
 }
 
-fn parse_transaction_amounts(env: Env, node: &Node) -> Result<Option<TransactionAmounts>, String> {
+fn parse_transaction_amounts(
+  values: &ValueContext,
+  node: &Node,
+  diagnostics: &Diagnostics,
+) -> Result<Option<TransactionAmounts>, ParseError> {
   node
     .children()
     .find(|node| node.has_tag_name("transactionAmounts"))
     .map(|amounts_node| {
-      let shares = get_value_footnotes(env, &amounts_node, "transactionShares");
-      let price_per_share = get_value_footnotes(env, &amounts_node, "transactionPricePerShare");
-      let acquired_disposed_code =
-        get_value_footnotes(env, &amounts_node, "transactionAcquiredDisposedCode");
+      diagnostics.unknown_elements(&amounts_node, TRANSACTION_AMOUNTS_ELEMENTS)?;
+      let shares =
+        get_value_footnotes::<f64, _>(values, &amounts_node, "transactionShares", diagnostics)?;
+      let price_per_share = get_value_footnotes::<f64, _>(
+        values,
+        &amounts_node,
+        "transactionPricePerShare",
+        diagnostics,
+      )?;
+      let acquired_disposed_code = get_value_footnotes::<AcquiredDisposed, _>(
+        values,
+        &amounts_node,
+        "transactionAcquiredDisposedCode",
+        diagnostics,
+      )?;
 
       Ok(TransactionAmounts {
         shares,
@@ -572,18 +1385,31 @@ fn parse_transaction_amounts(env: Env, node: &Node) -> Result<Option<Transaction
 }
 
 fn parse_derivative_transaction_amounts(
-  env: Env,
+  values: &ValueContext,
   node: &Node,
-) -> Result<Option<DerivativeTransactionAmounts>, String> {
+  diagnostics: &Diagnostics,
+) -> Result<Option<DerivativeTransactionAmounts>, ParseError> {
   node
     .children()
     .find(|node| node.has_tag_name("transactionAmounts"))
     .map(|amounts_node| {
-      let shares = get_value_footnotes(env, &amounts_node, "transactionShares");
-      let price_per_share = get_value_footnotes(env, &amounts_node, "transactionPricePerShare");
-      let total_value = get_value_footnotes(env, &amounts_node, "transactionTotalValue");
-      let acquired_disposed_code =
-        get_value_footnotes(env, &amounts_node, "transactionAcquiredDisposedCode");
+      diagnostics.unknown_elements(&amounts_node, DERIVATIVE_TRANSACTION_AMOUNTS_ELEMENTS)?;
+      let shares =
+        get_value_footnotes::<f64, _>(values, &amounts_node, "transactionShares", diagnostics)?;
+      let price_per_share = get_value_footnotes::<f64, _>(
+        values,
+        &amounts_node,
+        "transactionPricePerShare",
+        diagnostics,
+      )?;
+      let total_value =
+        get_value_footnotes::<f64, _>(values, &amounts_node, "transactionTotalValue", diagnostics)?;
+      let acquired_disposed_code = get_value_footnotes::<AcquiredDisposed, _>(
+        values,
+        &amounts_node,
+        "transactionAcquiredDisposedCode",
+        diagnostics,
+      )?;
 
       Ok(DerivativeTransactionAmounts {
         shares,
@@ -595,14 +1421,34 @@ fn parse_derivative_transaction_amounts(
     .transpose()
 }
 
-fn parse_underlying_security(env: Env, node: &Node) -> Result<Option<UnderlyingSecurity>, String> {
+fn parse_underlying_security(
+  values: &ValueContext,
+  node: &Node,
+  diagnostics: &Diagnostics,
+) -> Result<Option<UnderlyingSecurity>, ParseError> {
   node
     .children()
     .find(|node| node.has_tag_name("underlyingSecurity"))
     .map(|security_node| {
-      let title = get_value_footnotes(env, &security_node, "underlyingSecurityTitle");
-      let shares = get_value_footnotes(env, &security_node, "underlyingSecurityShares");
-      let value = get_value_footnotes(env, &security_node, "underlyingSecurityValue");
+      diagnostics.unknown_elements(&security_node, UNDERLYING_SECURITY_ELEMENTS)?;
+      let title = get_value_footnotes::<String, _>(
+        values,
+        &security_node,
+        "underlyingSecurityTitle",
+        diagnostics,
+      )?;
+      let shares = get_value_footnotes::<f64, _>(
+        values,
+        &security_node,
+        "underlyingSecurityShares",
+        diagnostics,
+      )?;
+      let value = get_value_footnotes::<f64, _>(
+        values,
+        &security_node,
+        "underlyingSecurityValue",
+        diagnostics,
+      )?;
 
       Ok(UnderlyingSecurity {
         title,
@@ -614,17 +1460,27 @@ fn parse_underlying_security(env: Env, node: &Node) -> Result<Option<UnderlyingS
 }
 
 fn parse_post_transaction_amounts(
-  env: Env,
+  values: &ValueContext,
   node: &Node,
-) -> Result<Option<PostTransactionAmounts>, String> {
+  diagnostics: &Diagnostics,
+) -> Result<Option<PostTransactionAmounts>, ParseError> {
   node
     .children()
     .find(|node| node.has_tag_name("postTransactionAmounts"))
     .map(|amounts_node| {
-      let shares_owned_following_transaction =
-        get_value_footnotes(env, &amounts_node, "sharesOwnedFollowingTransaction");
-      let value_owned_following_transaction =
-        get_value_footnotes(env, &amounts_node, "valueOwnedFollowingTransaction");
+      diagnostics.unknown_elements(&amounts_node, POST_TRANSACTION_AMOUNTS_ELEMENTS)?;
+      let shares_owned_following_transaction = get_value_footnotes::<f64, _>(
+        values,
+        &amounts_node,
+        "sharesOwnedFollowingTransaction",
+        diagnostics,
+      )?;
+      let value_owned_following_transaction = get_value_footnotes::<f64, _>(
+        values,
+        &amounts_node,
+        "valueOwnedFollowingTransaction",
+        diagnostics,
+      )?;
 
       Ok(PostTransactionAmounts {
         shares_owned_following_transaction,
@@ -634,14 +1490,24 @@ fn parse_post_transaction_amounts(
     .transpose()
 }
 
-fn parse_ownership_nature(env: Env, node: &Node) -> Result<Option<OwnershipNature>, String> {
+fn parse_ownership_nature(
+  values: &ValueContext,
+  node: &Node,
+  diagnostics: &Diagnostics,
+) -> Result<Option<OwnershipNature>, ParseError> {
   node
     .children()
     .find(|node| node.has_tag_name("ownershipNature"))
     .map(|nature_node| {
-      let direct_or_indirect_ownership =
-        get_value_footnotes(env, &nature_node, "directOrIndirectOwnership");
-      let nature_of_ownership = get_value_footnotes(env, &nature_node, "natureOfOwnership");
+      diagnostics.unknown_elements(&nature_node, OWNERSHIP_NATURE_ELEMENTS)?;
+      let direct_or_indirect_ownership = get_value_footnotes::<DirectOrIndirect, _>(
+        values,
+        &nature_node,
+        "directOrIndirectOwnership",
+        diagnostics,
+      )?;
+      let nature_of_ownership =
+        get_value_footnotes::<String, _>(values, &nature_node, "natureOfOwnership", diagnostics)?;
 
       Ok(OwnershipNature {
         direct_or_indirect_ownership,
@@ -651,35 +1517,73 @@ fn parse_ownership_nature(env: Env, node: &Node) -> Result<Option<OwnershipNatur
     .transpose()
 }
 
-fn parse_footnotes(node: &Node) -> Result<Vec<Footnote>, String> {
-  let footnotes = node
+fn parse_footnotes(
+  node: &Node,
+  resolve_footnotes: bool,
+  diagnostics: &Diagnostics,
+) -> Result<Vec<Footnote>, ParseError> {
+  for footnotes_node in node
+    .children()
+    .filter(|node| node.has_tag_name("footnotes"))
+  {
+    diagnostics.unknown_elements(&footnotes_node, &["footnote"])?;
+  }
+  let mut footnotes: Vec<Footnote> = node
     .children()
     .filter(|node| node.has_tag_name("footnotes"))
     .flat_map(|node| node.children())
     .filter(|node| node.has_tag_name("footnote"))
-    .filter_map(|footnote_node| {
+    .map(|footnote_node| {
       let id = footnote_node.attribute("id").map(|id| id.to_string());
       let note = footnote_node.text().map(|text| text.to_string());
+      let references = resolve_footnotes.then(Vec::new);
 
-      Some(Footnote { id, note })
+      Footnote {
+        id,
+        note,
+        references,
+      }
     })
     .collect();
 
+  if resolve_footnotes {
+    for id_node in node
+      .descendants()
+      .filter(|node| node.has_tag_name("footnoteId"))
+    {
+      let id = id_node.attribute("id").unwrap_or_default();
+      let Some(field_node) = id_node.parent_element() else {
+        continue;
+      };
+      match footnotes.iter_mut().find(|f| f.id.as_deref() == Some(id)) {
+        Some(footnote) => {
+          let references = footnote.references.get_or_insert_with(Vec::new);
+          let path = node_path(&field_node);
+          if !references.contains(&path) {
+            references.push(path);
+          }
+        }
+        None => {
+          let reason = "no footnote with this id".to_owned();
+          diagnostics.recover(ParseError::invalid(&id_node, id, reason))?
+        }
+      }
+    }
+  }
+
   Ok(footnotes)
 }
 
-fn parse_owner_signatures(node: &Node) -> Result<Vec<OwnerSignature>, String> {
-  node
-    .children()
-    .filter(|node| node.has_tag_name("ownerSignature"))
-    .map(|signature_node| {
-      let name = parse_string::<String>(&signature_node, "signatureName")
-        .ok_or_else(|| "signatureName not found".to_string())?;
-      let date = parse_string::<String>(&signature_node, "signatureDate")
-        .ok_or_else(|| "signatureDate not found".to_string())?;
-      Ok(OwnerSignature { name, date })
-    })
-    .collect()
+fn parse_owner_signatures(
+  node: &Node,
+  diagnostics: &Diagnostics,
+) -> Result<Vec<OwnerSignature>, ParseError> {
+  parse_rows(node, "ownerSignature", diagnostics, |signature_node| {
+    diagnostics.unknown_elements(signature_node, &["signatureName", "signatureDate"])?;
+    let name = parse_required::<String>(signature_node, "signatureName")?;
+    let date = parse_required::<String>(signature_node, "signatureDate")?;
+    Ok(OwnerSignature { name, date })
+  })
 }
 
 fn parse_footnote_ids(node: &Node) -> Option<Vec<String>> {
@@ -693,26 +1597,22 @@ fn parse_footnote_ids(node: &Node) -> Option<Vec<String>> {
   Some(footnote_ids)
 }
 
-fn get_value_footnotes(env: Env, node: &Node, tag: &str) -> Option<ValueFootnotes> {
+fn get_value_footnotes<T: ParseFromString, F: FromValueFootnotes<T::Output>>(
+  values: &ValueContext,
+  node: &Node,
+  tag: &str,
+  diagnostics: &Diagnostics,
+) -> Result<Option<F>, ParseError> {
   node
     .children()
     .find(|node| node.has_tag_name(tag))
     .map(|tag_node| {
-      let value = tag_node
-        .children()
-        .find(|child_node| child_node.has_tag_name("value"))
-        .map(|value_node| {
-          let text = value_node.text().unwrap_or_else(|| "");
-          parse_value(env, &text)
-        })
-        .transpose()
-        .unwrap_or(None);
-
+      diagnostics.unknown_elements(&tag_node, &["value", "footnoteId"])?;
+      let value = parse_optional::<T>(&tag_node, "value", diagnostics)?;
       let footnote_ids = parse_footnote_ids(&tag_node);
+      let footnotes = values.resolve(&footnote_ids);
 
-      ValueFootnotes {
-        value,
-        footnote_ids,
-      }
+      Ok(F::from_value_footnotes(value, footnote_ids, footnotes))
     })
+    .transpose()
 }
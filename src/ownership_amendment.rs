@@ -0,0 +1,275 @@
+use napi::{Error, Status};
+use napi_derive::napi;
+use std::collections::{HashMap, VecDeque};
+
+use crate::ownership::{
+  DerivativeHolding, DerivativeTable, DerivativeTransaction, FootnoteRefs, NonDerivativeHolding,
+  NonDerivativeTable, NonDerivativeTransaction, OwnershipForm, OwnershipNature, OwnershipTableKind,
+  StringFootnotes,
+};
+
+#[napi(object)]
+pub struct OwnershipAmendment {
+  pub non_derivative_table: Option<NonDerivativeTable>,
+  pub derivative_table: Option<DerivativeTable>,
+  pub changes: Vec<RowChange>,
+}
+
+#[napi(object)]
+pub struct RowChange {
+  pub kind: RowChangeKind,
+  pub table: OwnershipTableKind,
+  /// Index of the row in the original form, absent for added rows.
+  pub original_index: Option<u32>,
+  /// Index of the row in the amended form, absent for removed rows.
+  pub amended_index: Option<u32>,
+}
+
+#[napi(string_enum)]
+pub enum RowChangeKind {
+  Added,
+  Changed,
+  Removed,
+}
+
+/// Applies a Form 3/4/5 amendment to the original form. Amendments restate the complete form, so
+/// the amended tables are the effective ones; rows are matched to the original by security,
+/// date, code and ownership nature to report what the amendment added, changed or removed.
+#[napi]
+pub fn apply_ownership_amendment(
+  original: OwnershipForm,
+  amended: OwnershipForm,
+) -> Result<OwnershipAmendment, Error> {
+  if !amended.is_amendment() {
+    return Err(Error::new(
+      Status::InvalidArg,
+      format!("form {} is not an amendment", amended.document_type),
+    ));
+  }
+  if original.issuer.cik != amended.issuer.cik {
+    return Err(Error::new(
+      Status::InvalidArg,
+      format!(
+        "amendment issuer {} does not match original issuer {}",
+        amended.issuer.cik, original.issuer.cik
+      ),
+    ));
+  }
+
+  let notes = (footnote_texts(&original), footnote_texts(&amended));
+  let mut changes = vec![];
+  let original_non_derivative = original.non_derivative_table.as_ref();
+  let amended_non_derivative = amended.non_derivative_table.as_ref();
+  diff_rows(
+    OwnershipTableKind::NonDerivativeTransaction,
+    original_non_derivative.map_or(&[], |table| &table.transactions),
+    amended_non_derivative.map_or(&[], |table| &table.transactions),
+    &notes,
+    &mut changes,
+  );
+  diff_rows(
+    OwnershipTableKind::NonDerivativeHolding,
+    original_non_derivative.map_or(&[], |table| &table.holdings),
+    amended_non_derivative.map_or(&[], |table| &table.holdings),
+    &notes,
+    &mut changes,
+  );
+  let original_derivative = original.derivative_table.as_ref();
+  let amended_derivative = amended.derivative_table.as_ref();
+  diff_rows(
+    OwnershipTableKind::DerivativeTransaction,
+    original_derivative.map_or(&[], |table| &table.transactions),
+    amended_derivative.map_or(&[], |table| &table.transactions),
+    &notes,
+    &mut changes,
+  );
+  diff_rows(
+    OwnershipTableKind::DerivativeHolding,
+    original_derivative.map_or(&[], |table| &table.holdings),
+    amended_derivative.map_or(&[], |table| &table.holdings),
+    &notes,
+    &mut changes,
+  );
+
+  Ok(OwnershipAmendment {
+    non_derivative_table: amended.non_derivative_table,
+    derivative_table: amended.derivative_table,
+    changes,
+  })
+}
+
+/// Identifies a row across an original form and its amendment.
+trait AmendableRow: Clone + PartialEq + FootnoteRefs {
+  fn key(&self) -> String;
+}
+
+impl AmendableRow for NonDerivativeTransaction {
+  fn key(&self) -> String {
+    format!(
+      "{:?}|{:?}|{:?}|{:?}|{:?}",
+      title(&self.security_title),
+      self
+        .transaction_date
+        .as_ref()
+        .and_then(|date| date.value.as_ref()),
+      self
+        .transaction_coding
+        .as_ref()
+        .and_then(|coding| coding.transaction_code),
+      self
+        .transaction_amounts
+        .as_ref()
+        .and_then(|amounts| amounts.acquired_disposed_code.as_ref())
+        .and_then(|code| code.value),
+      direct_or_indirect(&self.ownership_nature),
+    )
+  }
+}
+
+impl AmendableRow for NonDerivativeHolding {
+  fn key(&self) -> String {
+    format!(
+      "{:?}|{:?}|{:?}",
+      title(&self.security_title),
+      direct_or_indirect(&self.ownership_nature),
+      nature_of_ownership(&self.ownership_nature),
+    )
+  }
+}
+
+impl AmendableRow for DerivativeTransaction {
+  fn key(&self) -> String {
+    format!(
+      "{:?}|{:?}|{:?}|{:?}|{:?}|{:?}|{:?}",
+      title(&self.security_title),
+      self
+        .conversion_or_exercise_price
+        .as_ref()
+        .and_then(|price| price.value),
+      self
+        .expiration_date
+        .as_ref()
+        .and_then(|date| date.value.as_ref()),
+      self
+        .transaction_date
+        .as_ref()
+        .and_then(|date| date.value.as_ref()),
+      self
+        .transaction_coding
+        .as_ref()
+        .and_then(|coding| coding.transaction_code),
+      self
+        .transaction_amounts
+        .as_ref()
+        .and_then(|amounts| amounts.acquired_disposed_code.as_ref())
+        .and_then(|code| code.value),
+      direct_or_indirect(&self.ownership_nature),
+    )
+  }
+}
+
+impl AmendableRow for DerivativeHolding {
+  fn key(&self) -> String {
+    format!(
+      "{:?}|{:?}|{:?}|{:?}",
+      title(&self.security_title),
+      self
+        .conversion_or_exercise_price
+        .as_ref()
+        .and_then(|price| price.value),
+      self
+        .expiration_date
+        .as_ref()
+        .and_then(|date| date.value.as_ref()),
+      direct_or_indirect(&self.ownership_nature),
+    )
+  }
+}
+
+/// Pairs the n-th original row with the n-th amended row sharing its key, so repeated rows such
+/// as several sales on the same day are matched in filing order. Footnote ids are local to each
+/// form, so matched rows are compared by the text of the footnotes they reference.
+fn diff_rows<T: AmendableRow>(
+  table: OwnershipTableKind,
+  original: &[T],
+  amended: &[T],
+  (original_notes, amended_notes): &(HashMap<&str, &str>, HashMap<&str, &str>),
+  changes: &mut Vec<RowChange>,
+) {
+  let mut unmatched: HashMap<String, VecDeque<usize>> = HashMap::new();
+  for (index, row) in original.iter().enumerate() {
+    unmatched.entry(row.key()).or_default().push_back(index);
+  }
+
+  for (amended_index, row) in amended.iter().enumerate() {
+    let original_index = unmatched
+      .get_mut(&row.key())
+      .and_then(|indices| indices.pop_front());
+    let kind = match original_index {
+      Some(index)
+        if with_footnote_text(&original[index], original_notes)
+          == with_footnote_text(row, amended_notes) =>
+      {
+        continue
+      }
+      Some(_) => RowChangeKind::Changed,
+      None => RowChangeKind::Added,
+    };
+    changes.push(RowChange {
+      kind,
+      table,
+      original_index: original_index.map(|index| index as u32),
+      amended_index: Some(amended_index as u32),
+    });
+  }
+
+  let mut removed: Vec<usize> = unmatched.into_values().flatten().collect();
+  removed.sort_unstable();
+  changes.extend(removed.into_iter().map(|index| RowChange {
+    kind: RowChangeKind::Removed,
+    table,
+    original_index: Some(index as u32),
+    amended_index: None,
+  }));
+}
+
+fn footnote_texts(form: &OwnershipForm) -> HashMap<&str, &str> {
+  form
+    .footnotes
+    .iter()
+    .filter_map(|footnote| Some((footnote.id.as_deref()?, footnote.note.as_deref()?.trim())))
+    .collect()
+}
+
+/// The row with each footnote id replaced by the text of its footnote.
+fn with_footnote_text<T: AmendableRow>(row: &T, notes: &HashMap<&str, &str>) -> T {
+  let mut row = row.clone();
+  row.footnote_refs_mut(&mut |id| {
+    if let Some(note) = notes.get(id.as_str()) {
+      *id = (*note).to_owned();
+    }
+  });
+  row
+}
+
+fn title(security_title: &Option<StringFootnotes>) -> Option<&str> {
+  security_title
+    .as_ref()
+    .and_then(|title| title.value.as_deref())
+    .map(str::trim)
+}
+
+fn direct_or_indirect(nature: &Option<OwnershipNature>) -> Option<&'static str> {
+  nature
+    .as_ref()
+    .and_then(|nature| nature.direct_or_indirect_ownership.as_ref())
+    .and_then(|ownership| ownership.value)
+    .map(|value| value.as_str())
+}
+
+fn nature_of_ownership(nature: &Option<OwnershipNature>) -> Option<&str> {
+  nature
+    .as_ref()
+    .and_then(|nature| nature.nature_of_ownership.as_ref())
+    .and_then(|nature| nature.value.as_deref())
+}
@@ -0,0 +1,212 @@
+use napi_derive::napi;
+use std::collections::HashMap;
+
+use crate::ownership::{
+  AcquiredDisposed, DirectOrIndirect, FootnoteRefs, OwnershipForm, OwnershipTableKind, TableRow,
+  TransactionCode,
+};
+use crate::reporting_owners::designated_filer;
+
+/// A transaction or holding with its issuer and owner, flattened for tabular storage.
+#[napi(object)]
+#[derive(Clone)]
+pub struct OwnershipRow {
+  pub issuer_cik: String,
+  pub issuer_name: Option<String>,
+  pub issuer_trading_symbol: String,
+  pub owner_cik: Option<String>,
+  pub owner_name: Option<String>,
+  /// Whether the owner is the designated filer; keep only these rows to count each transaction
+  /// of a joint filing once.
+  pub is_designated_filer: bool,
+  pub table: OwnershipTableKind,
+  pub security_title: Option<String>,
+  pub transaction_date: Option<String>,
+  #[napi(
+    ts_type = "'P' | 'S' | 'V' | 'A' | 'D' | 'F' | 'I' | 'M' | 'C' | 'E' | 'H' | 'O' | 'X' | 'G' | 'L' | 'W' | 'Z' | 'J' | 'K' | 'U'"
+  )]
+  pub transaction_code: Option<TransactionCode>,
+  pub shares: Option<f64>,
+  pub price_per_share: Option<f64>,
+  #[napi(ts_type = "'A' | 'D'")]
+  pub acquired_disposed: Option<AcquiredDisposed>,
+  pub shares_owned_following: Option<f64>,
+  #[napi(ts_type = "'D' | 'I'")]
+  pub direct_or_indirect: Option<DirectOrIndirect>,
+  pub nature_of_ownership: Option<String>,
+  /// Texts of every footnote referenced by the row, as `F1: text; F2: text`.
+  pub footnotes: Option<String>,
+}
+
+const CSV_HEADER: [&str; 17] = [
+  "issuerCik",
+  "issuerName",
+  "issuerTradingSymbol",
+  "ownerCik",
+  "ownerName",
+  "isDesignatedFiler",
+  "table",
+  "securityTitle",
+  "transactionDate",
+  "transactionCode",
+  "shares",
+  "pricePerShare",
+  "acquiredDisposed",
+  "sharesOwnedFollowing",
+  "directOrIndirect",
+  "natureOfOwnership",
+  "footnotes",
+];
+
+/// Emits one row per reporting owner and transaction or holding, as attributed by
+/// `attributeOwners`. Each owner's rows come together, non-derivative rows first, in filing
+/// order.
+#[napi]
+pub fn flatten_ownership_form(form: OwnershipForm) -> Vec<OwnershipRow> {
+  ownership_rows(&form)
+}
+
+/// Writes rows as RFC 4180 CSV with a header line.
+#[napi]
+pub fn ownership_rows_to_csv(rows: Vec<OwnershipRow>) -> String {
+  let mut csv = CSV_HEADER.join(",");
+  csv.push_str("\r\n");
+  for row in &rows {
+    let fields = [
+      Some(row.issuer_cik.clone()),
+      row.issuer_name.clone(),
+      Some(row.issuer_trading_symbol.clone()),
+      row.owner_cik.clone(),
+      row.owner_name.clone(),
+      Some(row.is_designated_filer.to_string()),
+      Some(table_name(row.table).to_owned()),
+      row.security_title.clone(),
+      row.transaction_date.clone(),
+      row.transaction_code.map(|code| code.as_str().to_owned()),
+      row.shares.map(|shares| shares.to_string()),
+      row.price_per_share.map(|price| price.to_string()),
+      row.acquired_disposed.map(|code| code.as_str().to_owned()),
+      row.shares_owned_following.map(|shares| shares.to_string()),
+      row.direct_or_indirect.map(|code| code.as_str().to_owned()),
+      row.nature_of_ownership.clone(),
+      row.footnotes.clone(),
+    ];
+    let line: Vec<String> = fields
+      .iter()
+      .map(|field| escape_csv(field.as_deref().unwrap_or_default()))
+      .collect();
+    csv.push_str(&line.join(","));
+    csv.push_str("\r\n");
+  }
+
+  csv
+}
+
+pub fn ownership_rows(form: &OwnershipForm) -> Vec<OwnershipRow> {
+  let notes: HashMap<&str, &str> = form
+    .footnotes
+    .iter()
+    .filter_map(|footnote| Some((footnote.id.as_deref()?, footnote.note.as_deref()?)))
+    .collect();
+
+  let mut rows = vec![];
+  let mut push = |table: OwnershipTableKind, row: &dyn ExportRow| {
+    let fields = row.row_fields();
+    let footnotes: Vec<String> = collect_footnote_ids(row)
+      .iter()
+      .map(|id| match notes.get(id.as_str()) {
+        Some(note) => format!("{}: {}", id, note.trim()),
+        None => id.clone(),
+      })
+      .collect();
+
+    rows.push(OwnershipRow {
+      issuer_cik: form.issuer.cik.clone(),
+      issuer_name: form.issuer.name.clone(),
+      issuer_trading_symbol: form.issuer.trading_symbol.clone(),
+      owner_cik: None,
+      owner_name: None,
+      is_designated_filer: false,
+      table,
+      security_title: fields.security_title,
+      transaction_date: fields.transaction_date,
+      transaction_code: fields.transaction_code,
+      shares: fields.shares,
+      price_per_share: fields.price_per_share,
+      acquired_disposed: fields.acquired_disposed,
+      shares_owned_following: fields.shares_owned_following,
+      direct_or_indirect: fields.direct_or_indirect,
+      nature_of_ownership: fields.nature_of_ownership,
+      footnotes: (!footnotes.is_empty()).then(|| footnotes.join("; ")),
+    });
+  };
+
+  if let Some(table) = &form.non_derivative_table {
+    for transaction in &table.transactions {
+      push(OwnershipTableKind::NonDerivativeTransaction, transaction);
+    }
+    for holding in &table.holdings {
+      push(OwnershipTableKind::NonDerivativeHolding, holding);
+    }
+  }
+  if let Some(table) = &form.derivative_table {
+    for transaction in &table.transactions {
+      push(OwnershipTableKind::DerivativeTransaction, transaction);
+    }
+    for holding in &table.holdings {
+      push(OwnershipTableKind::DerivativeHolding, holding);
+    }
+  }
+
+  if form.reporting_owners.is_empty() {
+    return rows;
+  }
+  let designated_cik = designated_filer(form).map(|owner| owner.id.cik.as_str());
+  form
+    .reporting_owners
+    .iter()
+    .flat_map(|owner| {
+      rows.iter().map(move |row| OwnershipRow {
+        owner_cik: Some(owner.id.cik.clone()),
+        owner_name: owner.id.name.clone(),
+        is_designated_filer: Some(owner.id.cik.as_str()) == designated_cik,
+        ..row.clone()
+      })
+    })
+    .collect()
+}
+
+/// A table row whose values and footnote references are exported.
+trait ExportRow: TableRow + FootnoteRefs {}
+
+impl<T: TableRow + FootnoteRefs> ExportRow for T {}
+
+/// Footnote ids in order of first reference.
+fn collect_footnote_ids(row: &dyn FootnoteRefs) -> Vec<String> {
+  let mut ids = vec![];
+  row.footnote_refs(&mut ids);
+  let mut footnote_ids: Vec<String> = vec![];
+  for id in ids {
+    if !footnote_ids.contains(id) {
+      footnote_ids.push(id.clone());
+    }
+  }
+  footnote_ids
+}
+
+fn table_name(table: OwnershipTableKind) -> &'static str {
+  match table {
+    OwnershipTableKind::NonDerivativeTransaction => "NonDerivativeTransaction",
+    OwnershipTableKind::NonDerivativeHolding => "NonDerivativeHolding",
+    OwnershipTableKind::DerivativeTransaction => "DerivativeTransaction",
+    OwnershipTableKind::DerivativeHolding => "DerivativeHolding",
+  }
+}
+
+fn escape_csv(field: &str) -> String {
+  if field.contains([',', '"', '\r', '\n']) {
+    format!("\"{}\"", field.replace('"', "\"\""))
+  } else {
+    field.to_owned()
+  }
+}
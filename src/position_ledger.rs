@@ -0,0 +1,290 @@
+use napi::{Error, Status};
+use napi_derive::napi;
+
+use crate::ownership::{
+  DirectOrIndirect, OwnershipForm, OwnershipTableKind, RowFields, TransactionCode,
+};
+
+/// Reported and computed holdings may differ by rounding in the filing.
+const TOLERANCE: f64 = 1e-4;
+
+#[napi(object)]
+pub struct PositionLedger {
+  pub issuer_cik: String,
+  pub owner_cik: String,
+  /// One entry per transaction and holding, in filing order.
+  pub entries: Vec<LedgerEntry>,
+  /// Holdings after the last form, one per security and ownership nature.
+  pub positions: Vec<Position>,
+  pub discrepancy_count: u32,
+}
+
+#[napi(object)]
+pub struct LedgerEntry {
+  /// Index of the form in the input sequence.
+  pub form_index: u32,
+  pub period_of_report: String,
+  pub table: OwnershipTableKind,
+  /// Index of the row within its table.
+  pub row_index: u32,
+  pub transaction_date: Option<String>,
+  #[napi(
+    ts_type = "'P' | 'S' | 'V' | 'A' | 'D' | 'F' | 'I' | 'M' | 'C' | 'E' | 'H' | 'O' | 'X' | 'G' | 'L' | 'W' | 'Z' | 'J' | 'K' | 'U'"
+  )]
+  pub transaction_code: Option<TransactionCode>,
+  pub security_title: String,
+  #[napi(ts_type = "'D' | 'I'")]
+  pub direct_or_indirect: Option<DirectOrIndirect>,
+  pub nature_of_ownership: Option<String>,
+  /// Signed share change, absent for holdings.
+  pub change: Option<f64>,
+  /// Running holdings plus the change, absent for the first observation of a position and for
+  /// transactions whose change is unknown.
+  pub expected_shares: Option<f64>,
+  /// `sharesOwnedFollowingTransaction` as reported.
+  pub reported_shares: Option<f64>,
+  /// Holdings after the row; the reported amount when given, otherwise the expected one.
+  pub shares: Option<f64>,
+  /// Reported minus expected holdings when they disagree.
+  pub discrepancy: Option<f64>,
+}
+
+#[napi(object)]
+pub struct Position {
+  pub security_title: String,
+  pub derivative: bool,
+  #[napi(ts_type = "'D' | 'I'")]
+  pub direct_or_indirect: Option<DirectOrIndirect>,
+  pub nature_of_ownership: Option<String>,
+  /// Derivative positions with different terms are tracked apart.
+  pub conversion_or_exercise_price: Option<f64>,
+  pub expiration_date: Option<String>,
+  pub shares: Option<f64>,
+  pub period_of_report: String,
+}
+
+/// Replays the forms of one issuer and reporting owner in filing order, tracking holdings per
+/// security, ownership nature and, for derivatives, exercise price and expiration, and checking each reported post-transaction amount against the
+/// running total. The owner defaults to the one reporting owner every form lists. An amendment
+/// restates its form, so it replaces the latest earlier form of the same type and period.
+#[napi]
+pub fn build_position_ledger(
+  forms: Vec<OwnershipForm>,
+  owner_cik: Option<String>,
+) -> Result<PositionLedger, Error> {
+  let first = forms
+    .first()
+    .ok_or_else(|| Error::new(Status::InvalidArg, "no forms to replay".to_owned()))?;
+  let issuer_cik = first.issuer.cik.clone();
+  let owner_cik = match owner_cik {
+    Some(owner_cik) => owner_cik,
+    None => shared_owner_cik(&forms)?,
+  };
+  for form in &forms {
+    if form.issuer.cik != issuer_cik {
+      return Err(Error::new(
+        Status::InvalidArg,
+        format!(
+          "form issuer {} does not match ledger issuer {}",
+          form.issuer.cik, issuer_cik
+        ),
+      ));
+    }
+    if !lists_owner(form, &owner_cik) {
+      return Err(Error::new(
+        Status::InvalidArg,
+        format!(
+          "form {} for {} does not list ledger owner {owner_cik}",
+          form.document_type, form.period_of_report
+        ),
+      ));
+    }
+  }
+
+  let mut ledger = Ledger::default();
+  for (form_index, form) in effective_forms(&forms)? {
+    for (table, row_index, fields) in form.table_rows() {
+      ledger.record(form_index, form, table, row_index, fields);
+    }
+  }
+
+  let discrepancy_count = ledger
+    .entries
+    .iter()
+    .filter(|entry| entry.discrepancy.is_some())
+    .count() as u32;
+
+  Ok(PositionLedger {
+    issuer_cik,
+    owner_cik,
+    entries: ledger.entries,
+    positions: ledger.positions,
+    discrepancy_count,
+  })
+}
+
+#[derive(Default)]
+struct Ledger {
+  entries: Vec<LedgerEntry>,
+  positions: Vec<Position>,
+}
+
+impl Ledger {
+  fn record(
+    &mut self,
+    form_index: usize,
+    form: &OwnershipForm,
+    table: OwnershipTableKind,
+    row_index: usize,
+    fields: RowFields,
+  ) {
+    let security_title = fields
+      .security_title
+      .as_deref()
+      .map(str::trim)
+      .unwrap_or_default()
+      .to_owned();
+    let derivative = matches!(
+      table,
+      OwnershipTableKind::DerivativeTransaction | OwnershipTableKind::DerivativeHolding
+    );
+    let direct_or_indirect = fields.direct_or_indirect;
+    let nature_of_ownership = fields
+      .nature_of_ownership
+      .as_deref()
+      .map(|nature| nature.trim().to_owned());
+    let expiration_date = fields
+      .expiration_date
+      .as_deref()
+      .map(|date| date.trim().to_owned());
+    let reported_shares = fields.shares_owned_following;
+    let change = fields.share_change();
+    let holding = matches!(
+      table,
+      OwnershipTableKind::NonDerivativeHolding | OwnershipTableKind::DerivativeHolding
+    );
+
+    let position = match self.positions.iter().position(|position| {
+      position.derivative == derivative
+        && position.security_title == security_title
+        && position.direct_or_indirect == direct_or_indirect
+        && position.nature_of_ownership == nature_of_ownership
+        && position.conversion_or_exercise_price == fields.conversion_or_exercise_price
+        && position.expiration_date == expiration_date
+    }) {
+      Some(index) => &mut self.positions[index],
+      None => {
+        self.positions.push(Position {
+          security_title: security_title.clone(),
+          derivative,
+          direct_or_indirect,
+          nature_of_ownership: nature_of_ownership.clone(),
+          conversion_or_exercise_price: fields.conversion_or_exercise_price,
+          expiration_date,
+          shares: None,
+          period_of_report: form.period_of_report.clone(),
+        });
+        self.positions.last_mut().unwrap()
+      }
+    };
+
+    // A holding restates the position; a transaction of unknown size cannot be checked.
+    let expected_shares = match (position.shares, change) {
+      (Some(shares), Some(change)) => Some(shares + change),
+      (Some(shares), None) if holding => Some(shares),
+      _ => None,
+    };
+    let discrepancy = match (reported_shares, expected_shares) {
+      (Some(reported), Some(expected)) if (reported - expected).abs() > TOLERANCE => {
+        Some(reported - expected)
+      }
+      _ => None,
+    };
+    let shares = reported_shares.or(expected_shares);
+    position.shares = shares;
+    position.period_of_report = form.period_of_report.clone();
+
+    self.entries.push(LedgerEntry {
+      form_index: form_index as u32,
+      period_of_report: form.period_of_report.clone(),
+      table,
+      row_index: row_index as u32,
+      transaction_date: fields.transaction_date,
+      transaction_code: fields.transaction_code,
+      security_title,
+      direct_or_indirect,
+      nature_of_ownership,
+      change,
+      expected_shares,
+      reported_shares,
+      shares,
+      discrepancy,
+    });
+  }
+}
+
+/// The forms to replay with their index in the input, amendments in place of their originals.
+fn effective_forms(forms: &[OwnershipForm]) -> Result<Vec<(usize, &OwnershipForm)>, Error> {
+  let mut effective: Vec<(usize, &OwnershipForm)> = vec![];
+  for (index, form) in forms.iter().enumerate() {
+    if !form.is_amendment() {
+      effective.push((index, form));
+      continue;
+    }
+    let original = effective
+      .iter_mut()
+      .rev()
+      .find(|(_, original)| {
+        base_document_type(original) == base_document_type(form)
+          && original.period_of_report == form.period_of_report
+      })
+      .ok_or_else(|| {
+        Error::new(
+          Status::InvalidArg,
+          format!(
+            "amendment {} for {} has no earlier form to replace",
+            form.document_type, form.period_of_report
+          ),
+        )
+      })?;
+    *original = (index, form);
+  }
+  Ok(effective)
+}
+
+fn base_document_type(form: &OwnershipForm) -> &str {
+  form
+    .document_type
+    .strip_suffix("/A")
+    .unwrap_or(&form.document_type)
+}
+
+fn lists_owner(form: &OwnershipForm, owner_cik: &str) -> bool {
+  form
+    .reporting_owners
+    .iter()
+    .any(|owner| owner.id.cik == owner_cik)
+}
+
+fn shared_owner_cik(forms: &[OwnershipForm]) -> Result<String, Error> {
+  let shared: Vec<&str> = forms[0]
+    .reporting_owners
+    .iter()
+    .map(|owner| owner.id.cik.as_str())
+    .filter(|cik| forms.iter().all(|form| lists_owner(form, cik)))
+    .collect();
+  match shared.as_slice() {
+    [owner_cik] => Ok((*owner_cik).to_owned()),
+    [] => Err(Error::new(
+      Status::InvalidArg,
+      "forms share no reporting owner".to_owned(),
+    )),
+    _ => Err(Error::new(
+      Status::InvalidArg,
+      format!(
+        "forms share reporting owners {}; pass the owner to replay",
+        shared.join(", ")
+      ),
+    )),
+  }
+}
@@ -0,0 +1,110 @@
+use napi_derive::napi;
+
+use crate::ownership::{
+  AcquiredDisposed, DirectOrIndirect, OwnershipForm, OwnershipTableKind, ReportingOwner,
+  ReportingOwnerID, TransactionCode,
+};
+
+#[napi(object)]
+pub struct OwnerAttribution {
+  /// The first listed owner, which EDGAR uses for the designated filer of a joint filing.
+  pub designated_filer: Option<ReportingOwnerID>,
+  pub directors: Vec<ReportingOwnerID>,
+  pub officers: Vec<ReportingOwnerID>,
+  pub ten_percent_owners: Vec<ReportingOwnerID>,
+  pub others: Vec<ReportingOwnerID>,
+  /// One row per reporting owner and transaction, in owner then filing order.
+  pub transactions: Vec<OwnerTransaction>,
+}
+
+#[napi(object)]
+pub struct OwnerTransaction {
+  pub owner_cik: String,
+  pub owner_name: Option<String>,
+  pub is_designated_filer: bool,
+  pub table: OwnershipTableKind,
+  /// Index of the transaction within its table.
+  pub index: u32,
+  pub security_title: Option<String>,
+  pub transaction_date: Option<String>,
+  #[napi(
+    ts_type = "'P' | 'S' | 'V' | 'A' | 'D' | 'F' | 'I' | 'M' | 'C' | 'E' | 'H' | 'O' | 'X' | 'G' | 'L' | 'W' | 'Z' | 'J' | 'K' | 'U'"
+  )]
+  pub transaction_code: Option<TransactionCode>,
+  pub shares: Option<f64>,
+  pub price_per_share: Option<f64>,
+  #[napi(ts_type = "'A' | 'D'")]
+  pub acquired_disposed: Option<AcquiredDisposed>,
+  pub shares_owned_following: Option<f64>,
+  #[napi(ts_type = "'D' | 'I'")]
+  pub direct_or_indirect: Option<DirectOrIndirect>,
+}
+
+/// Attributes the transactions of a joint filing to each reporting owner and groups the owners by
+/// their relationship to the issuer. An owner with several relationships appears in each group.
+#[napi]
+pub fn attribute_owners(form: OwnershipForm) -> OwnerAttribution {
+  let designated_filer = designated_filer(&form).map(|owner| owner.id.clone());
+  let group = |flag: fn(&ReportingOwner) -> Option<bool>| {
+    form
+      .reporting_owners
+      .iter()
+      .filter(|owner| flag(owner).unwrap_or(false))
+      .map(|owner| owner.id.clone())
+      .collect()
+  };
+
+  OwnerAttribution {
+    directors: group(|owner| owner.relationship.as_ref()?.is_director),
+    officers: group(|owner| owner.relationship.as_ref()?.is_officer),
+    ten_percent_owners: group(|owner| owner.relationship.as_ref()?.is_ten_percent_owner),
+    others: group(|owner| owner.relationship.as_ref()?.is_other),
+    transactions: owner_transactions(&form),
+    designated_filer,
+  }
+}
+
+/// The first listed reporting owner. EDGAR lists the designated filer of a joint filing first,
+/// but the XML does not mark it, so this is only the designated filer by that convention.
+pub fn designated_filer(form: &OwnershipForm) -> Option<&ReportingOwner> {
+  form.reporting_owners.first()
+}
+
+/// Repeats every non-derivative and derivative transaction once per reporting owner.
+pub fn owner_transactions(form: &OwnershipForm) -> Vec<OwnerTransaction> {
+  let designated_cik = designated_filer(form).map(|owner| owner.id.cik.as_str());
+  let transactions: Vec<_> = form
+    .table_rows()
+    .into_iter()
+    .filter(|(table, _, _)| {
+      matches!(
+        table,
+        OwnershipTableKind::NonDerivativeTransaction | OwnershipTableKind::DerivativeTransaction
+      )
+    })
+    .collect();
+
+  form
+    .reporting_owners
+    .iter()
+    .flat_map(|owner| {
+      transactions
+        .iter()
+        .map(move |(table, index, fields)| OwnerTransaction {
+          owner_cik: owner.id.cik.clone(),
+          owner_name: owner.id.name.clone(),
+          is_designated_filer: Some(owner.id.cik.as_str()) == designated_cik,
+          table: *table,
+          index: *index as u32,
+          security_title: fields.security_title.clone(),
+          transaction_date: fields.transaction_date.clone(),
+          transaction_code: fields.transaction_code,
+          shares: fields.shares,
+          price_per_share: fields.price_per_share,
+          acquired_disposed: fields.acquired_disposed,
+          shares_owned_following: fields.shares_owned_following,
+          direct_or_indirect: fields.direct_or_indirect,
+        })
+    })
+    .collect()
+}
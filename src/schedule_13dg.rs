@@ -0,0 +1,348 @@
+use napi::{Env, Error};
+use napi_derive::napi;
+use roxmltree::{Document as XMLDoc, Node};
+
+use crate::error::ParseError;
+use crate::{
+  find_required, parse_field, parse_optional, parse_required, parse_rows, parse_string, Diagnostic,
+  Diagnostics, ParseOptions, UsDate,
+};
+
+#[napi(object)]
+pub struct BeneficialOwnershipReport {
+  pub submission_type: String,
+  pub filer_cik: Option<String>,
+  pub amendment_no: Option<i32>,
+  pub securities_class_title: Option<String>,
+  /// Date of the event which requires filing of the statement.
+  pub event_date: Option<String>,
+  pub issuer: ScheduleIssuer,
+  pub reporting_persons: Vec<ReportingPerson>,
+  pub items: Vec<ItemResponse>,
+  pub warnings: Vec<Diagnostic>,
+}
+
+#[napi(object)]
+pub struct ScheduleIssuer {
+  pub cik: String,
+  pub name: String,
+  pub cusip: Option<String>,
+}
+
+/// The cover page filed for each reporting person.
+#[napi(object)]
+pub struct ReportingPerson {
+  pub cik: Option<String>,
+  pub name: String,
+  pub citizenship_or_organization: Option<String>,
+  pub sole_voting_power: Option<f64>,
+  pub shared_voting_power: Option<f64>,
+  pub sole_dispositive_power: Option<f64>,
+  pub shared_dispositive_power: Option<f64>,
+  pub aggregate_amount_owned: Option<f64>,
+  pub percent_of_class: Option<f64>,
+  /// Codes such as `IN`, `IA` or `CO`.
+  pub type_of_reporting_person: Vec<String>,
+}
+
+#[napi(object)]
+pub struct ItemResponse {
+  /// Item number as printed on the schedule, e.g. `4` for the purpose of the transaction.
+  pub number: u32,
+  pub fields: Vec<ItemField>,
+}
+
+#[napi(object)]
+pub struct ItemField {
+  /// Path of the element relative to the item, e.g. `numberOfSharesPersonHas/solePowerOrDirectToVote`.
+  pub path: String,
+  pub value: String,
+}
+
+/// Element names that differ between the Schedule 13D and 13G XML schemas.
+struct ScheduleLayout {
+  event_date: &'static str,
+  issuer_cik: &'static str,
+  issuer_cusip: &'static str,
+  reporting_persons: Option<&'static str>,
+  reporting_person: &'static str,
+  reporting_person_cik: &'static str,
+  powers: Option<&'static str>,
+  aggregate_amount_owned: &'static str,
+  percent_of_class: &'static str,
+  items: &'static str,
+  form_data_elements: &'static [&'static str],
+  cover_page_elements: &'static [&'static str],
+  issuer_elements: &'static [&'static str],
+  reporting_person_elements: &'static [&'static str],
+}
+
+const SCHEDULE_13D: ScheduleLayout = ScheduleLayout {
+  event_date: "dateOfEvent",
+  issuer_cik: "issuerCIK",
+  issuer_cusip: "issuerCUSIP",
+  reporting_persons: Some("reportingPersons"),
+  reporting_person: "reportingPersonInfo",
+  reporting_person_cik: "reportingPersonCIK",
+  powers: None,
+  aggregate_amount_owned: "aggregateAmountOwned",
+  percent_of_class: "percentOfClass",
+  items: "items1To7",
+  form_data_elements: &[
+    "coverPageHeader",
+    "reportingPersons",
+    "items1To7",
+    "signatureInfo",
+  ],
+  cover_page_elements: &[
+    "amendmentNo",
+    "securitiesClassTitle",
+    "dateOfEvent",
+    "previouslyFiledFlag",
+    "issuerInfo",
+  ],
+  issuer_elements: &["issuerCIK", "issuerCUSIP", "issuerName"],
+  reporting_person_elements: &[
+    "reportingPersonCIK",
+    "reportingPersonName",
+    "memberOfGroup",
+    "fundType",
+    "citizenshipOrOrganization",
+    "soleVotingPower",
+    "sharedVotingPower",
+    "soleDispositivePower",
+    "sharedDispositivePower",
+    "aggregateAmountOwned",
+    "isAggregateExcludeShares",
+    "percentOfClass",
+    "typeOfReportingPerson",
+  ],
+};
+
+const SCHEDULE_13G: ScheduleLayout = ScheduleLayout {
+  event_date: "eventDateRequiresFilingThisStatement",
+  issuer_cik: "issuerCik",
+  issuer_cusip: "issuerCusip",
+  reporting_persons: None,
+  reporting_person: "coverPageHeaderReportingPersonDetails",
+  reporting_person_cik: "reportingPersonCik",
+  powers: Some("reportingPersonBeneficiallyOwnedNumberOfShares"),
+  aggregate_amount_owned: "reportingPersonBeneficiallyOwnedAggregateNumberOfShares",
+  percent_of_class: "classPercent",
+  items: "items",
+  form_data_elements: &[
+    "coverPageHeader",
+    "coverPageHeaderReportingPersonDetails",
+    "items",
+    "signatureInformation",
+  ],
+  cover_page_elements: &[
+    "amendmentNo",
+    "securitiesClassTitle",
+    "eventDateRequiresFilingThisStatement",
+    "issuerInfo",
+    "designateRulesPursuantToWhichThisScheduleIsFiled",
+  ],
+  issuer_elements: &["issuerCik", "issuerName", "issuerCusip"],
+  reporting_person_elements: &[
+    "reportingPersonCik",
+    "reportingPersonName",
+    "memberGroup",
+    "citizenshipOrOrganization",
+    "reportingPersonBeneficiallyOwnedNumberOfShares",
+    "reportingPersonBeneficiallyOwnedAggregateNumberOfShares",
+    "isAggregateExcludeShares",
+    "classPercent",
+    "typeOfReportingPerson",
+  ],
+};
+
+#[napi]
+pub fn parse_schedule13d(
+  env: Env,
+  schedule: String,
+  options: Option<ParseOptions>,
+) -> Result<BeneficialOwnershipReport, Error> {
+  parse_schedule(env, &schedule, options, &SCHEDULE_13D)
+}
+
+#[napi]
+pub fn parse_schedule13g(
+  env: Env,
+  schedule: String,
+  options: Option<ParseOptions>,
+) -> Result<BeneficialOwnershipReport, Error> {
+  parse_schedule(env, &schedule, options, &SCHEDULE_13G)
+}
+
+fn parse_schedule(
+  env: Env,
+  schedule: &str,
+  options: Option<ParseOptions>,
+  layout: &ScheduleLayout,
+) -> Result<BeneficialOwnershipReport, Error> {
+  let doc = XMLDoc::parse(schedule).map_err(|e| ParseError::from(e).into_napi(env))?;
+  let diagnostics = &Diagnostics::new(ParseOptions::mode(&options));
+  parse_schedule_document(&doc.root_element(), layout, diagnostics).map_err(|e| e.into_napi(env))
+}
+
+fn parse_schedule_document(
+  root: &Node,
+  layout: &ScheduleLayout,
+  diagnostics: &Diagnostics,
+) -> Result<BeneficialOwnershipReport, ParseError> {
+  let header_node = find_required(root, "headerData")?;
+  let submission_type = parse_field::<String>(&header_node, "submissionType", diagnostics)?;
+  let filer_cik = header_node
+    .descendants()
+    .find(|node| node.has_tag_name("cik"))
+    .and_then(|node| node.text())
+    .map(ToString::to_string);
+
+  let form_data_node = find_required(root, "formData")?;
+  diagnostics.unknown_elements(&form_data_node, layout.form_data_elements)?;
+  let cover_page_node = find_required(&form_data_node, "coverPageHeader")?;
+  diagnostics.unknown_elements(&cover_page_node, layout.cover_page_elements)?;
+  let amendment_no = parse_optional::<i32>(&cover_page_node, "amendmentNo", diagnostics)?;
+  let securities_class_title = parse_string::<String>(&cover_page_node, "securitiesClassTitle");
+  let event_date = parse_optional::<UsDate>(&cover_page_node, layout.event_date, diagnostics)?;
+  let issuer = parse_issuer(&cover_page_node, layout, diagnostics)?;
+  let reporting_persons = parse_reporting_persons(&form_data_node, layout, diagnostics)?;
+  let items = parse_items(&form_data_node, layout);
+
+  Ok(BeneficialOwnershipReport {
+    submission_type,
+    filer_cik,
+    amendment_no,
+    securities_class_title,
+    event_date,
+    issuer,
+    reporting_persons,
+    items,
+    warnings: diagnostics.take_warnings(),
+  })
+}
+
+fn parse_issuer(
+  node: &Node,
+  layout: &ScheduleLayout,
+  diagnostics: &Diagnostics,
+) -> Result<ScheduleIssuer, ParseError> {
+  find_required(node, "issuerInfo").and_then(|issuer_node| {
+    diagnostics.unknown_elements(&issuer_node, layout.issuer_elements)?;
+    let cik = parse_field::<String>(&issuer_node, layout.issuer_cik, diagnostics)?;
+    let name = parse_field::<String>(&issuer_node, "issuerName", diagnostics)?;
+    let cusip = parse_string::<String>(&issuer_node, layout.issuer_cusip);
+
+    Ok(ScheduleIssuer { cik, name, cusip })
+  })
+}
+
+fn parse_reporting_persons(
+  node: &Node,
+  layout: &ScheduleLayout,
+  diagnostics: &Diagnostics,
+) -> Result<Vec<ReportingPerson>, ParseError> {
+  let container_node = match layout.reporting_persons {
+    Some(tag) => find_required(node, tag)?,
+    None => *node,
+  };
+
+  let persons = parse_rows(
+    &container_node,
+    layout.reporting_person,
+    diagnostics,
+    |person_node| {
+      diagnostics.unknown_elements(person_node, layout.reporting_person_elements)?;
+      let cik = parse_string::<String>(person_node, layout.reporting_person_cik);
+      let name = parse_required::<String>(person_node, "reportingPersonName")?;
+      let citizenship_or_organization =
+        parse_string::<String>(person_node, "citizenshipOrOrganization");
+      let powers_node = match layout.powers {
+        Some(tag) => person_node
+          .children()
+          .find(|node| node.has_tag_name(tag))
+          .unwrap_or(*person_node),
+        None => *person_node,
+      };
+      let sole_voting_power = parse_optional::<f64>(&powers_node, "soleVotingPower", diagnostics)?;
+      let shared_voting_power =
+        parse_optional::<f64>(&powers_node, "sharedVotingPower", diagnostics)?;
+      let sole_dispositive_power =
+        parse_optional::<f64>(&powers_node, "soleDispositivePower", diagnostics)?;
+      let shared_dispositive_power =
+        parse_optional::<f64>(&powers_node, "sharedDispositivePower", diagnostics)?;
+      let aggregate_amount_owned =
+        parse_optional::<f64>(person_node, layout.aggregate_amount_owned, diagnostics)?;
+      let percent_of_class =
+        parse_optional::<f64>(person_node, layout.percent_of_class, diagnostics)?;
+      let type_of_reporting_person = person_node
+        .children()
+        .filter(|node| node.has_tag_name("typeOfReportingPerson"))
+        .filter_map(|node| node.text())
+        .map(|text| text.trim().to_owned())
+        .collect();
+
+      Ok(ReportingPerson {
+        cik,
+        name,
+        citizenship_or_organization,
+        sole_voting_power,
+        shared_voting_power,
+        sole_dispositive_power,
+        shared_dispositive_power,
+        aggregate_amount_owned,
+        percent_of_class,
+        type_of_reporting_person,
+      })
+    },
+  )?;
+
+  if persons.is_empty() {
+    Err(ParseError::missing(
+      &container_node,
+      layout.reporting_person,
+    ))
+  } else {
+    Ok(persons)
+  }
+}
+
+fn parse_items(node: &Node, layout: &ScheduleLayout) -> Vec<ItemResponse> {
+  node
+    .children()
+    .filter(|node| node.has_tag_name(layout.items))
+    .flat_map(|node| node.children())
+    .filter_map(|item_node| {
+      let number = item_node
+        .tag_name()
+        .name()
+        .strip_prefix("item")?
+        .parse::<u32>()
+        .ok()?;
+      let fields = item_node
+        .descendants()
+        .filter(|node| node.is_element() && !node.children().any(|child| child.is_element()))
+        .filter_map(|field_node| {
+          let value = field_node.text()?.trim();
+          if value.is_empty() {
+            return None;
+          }
+          let mut names: Vec<&str> = field_node
+            .ancestors()
+            .take_while(|ancestor| *ancestor != item_node)
+            .map(|ancestor| ancestor.tag_name().name())
+            .collect();
+          names.reverse();
+
+          Some(ItemField {
+            path: names.join("/"),
+            value: value.to_owned(),
+          })
+        })
+        .collect();
+
+      Some(ItemResponse { number, fields })
+    })
+    .collect()
+}
@@ -0,0 +1,197 @@
+use napi_derive::napi;
+use std::collections::HashMap;
+
+use crate::form_13f::Form13FTable;
+
+/// Identifiers derived from an information table entry.
+#[napi(object)]
+pub struct SecurityIdentifiers {
+  pub cusip: String,
+  /// Whether the CUSIP is well formed and its check digit matches.
+  pub cusip_valid: bool,
+  /// Absent when the entry has no FIGI.
+  pub figi_valid: Option<bool>,
+  /// The first six characters of a valid CUSIP, identifying the issuer.
+  pub issuer_cusip: Option<String>,
+  /// The US ISIN, absent for CINS numbers, which identify issuers outside North America.
+  pub isin: Option<String>,
+  /// From the lookup table, when one is given.
+  pub ticker: Option<String>,
+}
+
+/// Validates and derives identifiers for each entry, in table order. `tickers` maps CUSIPs to
+/// tickers, for example as read by `parseTickerCsv`.
+#[napi]
+pub fn enrich_form13f_table(
+  table: Form13FTable,
+  tickers: Option<HashMap<String, String>>,
+) -> Vec<SecurityIdentifiers> {
+  let tickers: HashMap<String, String> = tickers
+    .unwrap_or_default()
+    .into_iter()
+    .map(|(cusip, ticker)| (cusip.trim().to_uppercase(), ticker))
+    .collect();
+
+  table
+    .entries
+    .iter()
+    .map(|entry| {
+      let cusip = entry.cusip.trim().to_uppercase();
+      let cusip_valid = validate_cusip(cusip.clone());
+
+      SecurityIdentifiers {
+        figi_valid: entry.figi.clone().map(validate_figi),
+        issuer_cusip: issuer_cusip(cusip.clone()),
+        isin: cusip_to_isin(cusip.clone()),
+        ticker: tickers.get(&cusip).cloned(),
+        cusip_valid,
+        cusip,
+      }
+    })
+    .collect()
+}
+
+/// Checks the length, character set and check digit of a CUSIP.
+#[napi]
+pub fn validate_cusip(cusip: String) -> bool {
+  let cusip = cusip.trim().as_bytes();
+  cusip.len() == 9
+    && cusip[..8]
+      .iter()
+      .all(|c| c.is_ascii_digit() || c.is_ascii_uppercase() || b"*@#".contains(c))
+    && Some(cusip[8]) == check_digit(&cusip[..8])
+}
+
+/// Checks the structure and check digit of a Financial Instrument Global Identifier.
+#[napi]
+pub fn validate_figi(figi: String) -> bool {
+  let figi = figi.trim().as_bytes();
+  figi.len() == 12
+    && figi[..2].iter().all(|c| is_consonant(*c))
+    && !matches!(
+      &figi[..2],
+      b"BS" | b"BM" | b"GG" | b"GB" | b"GH" | b"KY" | b"VG"
+    )
+    && figi[2] == b'G'
+    && figi[3..11]
+      .iter()
+      .all(|c| c.is_ascii_digit() || is_consonant(*c))
+    && Some(figi[11]) == check_digit(&figi[..11])
+}
+
+/// The six-character issuer number of a valid CUSIP.
+#[napi]
+pub fn issuer_cusip(cusip: String) -> Option<String> {
+  validate_cusip(cusip.clone()).then(|| cusip.trim()[..6].to_owned())
+}
+
+/// Builds the US ISIN of a valid CUSIP. Canadian issuers, whose ISINs start with `CA`, cannot be
+/// told apart by CUSIP alone. CUSIPs using `*`, `@` or `#`, which are reserved for private
+/// placements and have no ISIN form, give none.
+#[napi]
+pub fn cusip_to_isin(cusip: String) -> Option<String> {
+  if !validate_cusip(cusip.clone()) {
+    return None;
+  }
+  let cusip = cusip.trim();
+  // CINS numbers for issuers outside North America start with a letter.
+  if !cusip.as_bytes()[0].is_ascii_digit() {
+    return None;
+  }
+  if cusip.contains(['*', '@', '#']) {
+    return None;
+  }
+
+  let base = format!("US{cusip}");
+  let digits: String = base
+    .chars()
+    .map(|c| c.to_digit(36).map(|digit| digit.to_string()))
+    .collect::<Option<_>>()?;
+  let sum: u32 = digits
+    .bytes()
+    .rev()
+    .enumerate()
+    .map(|(i, digit)| {
+      let value = u32::from(digit - b'0');
+      if i % 2 == 0 {
+        let doubled = value * 2;
+        doubled / 10 + doubled % 10
+      } else {
+        value
+      }
+    })
+    .sum();
+
+  Some(format!("{base}{}", (10 - sum % 10) % 10))
+}
+
+/// Reads a CUSIP to ticker table from CSV with the CUSIP in the first column and the ticker in
+/// the second. A header row and further columns are ignored. Fields may be quoted, with `""`
+/// standing for a quote inside a quoted field; records spanning lines are not supported.
+#[napi]
+pub fn parse_ticker_csv(csv: String) -> HashMap<String, String> {
+  csv
+    .lines()
+    .filter_map(|line| {
+      let mut fields = split_csv_line(line).into_iter();
+      let cusip = fields.next()?.trim().to_uppercase();
+      let ticker = fields.next()?.trim().to_owned();
+      if cusip.is_empty() || ticker.is_empty() || cusip == "CUSIP" {
+        return None;
+      }
+
+      Some((cusip, ticker))
+    })
+    .collect()
+}
+
+/// Splits one CSV record into fields, honouring quotes so that quoted commas stay in the field.
+fn split_csv_line(line: &str) -> Vec<String> {
+  let mut fields = Vec::new();
+  let mut field = String::new();
+  let mut quoted = false;
+  let mut chars = line.chars().peekable();
+  while let Some(c) = chars.next() {
+    match c {
+      '"' if quoted && chars.peek() == Some(&'"') => {
+        field.push('"');
+        chars.next();
+      }
+      '"' if quoted => quoted = false,
+      '"' if field.trim().is_empty() => {
+        field.clear();
+        quoted = true;
+      }
+      ',' if !quoted => fields.push(std::mem::take(&mut field)),
+      _ => field.push(c),
+    }
+  }
+  fields.push(field);
+
+  fields
+}
+
+/// The modified Luhn check digit shared by CUSIPs and FIGIs.
+fn check_digit(base: &[u8]) -> Option<u8> {
+  let mut sum = 0;
+  for (i, c) in base.iter().enumerate() {
+    let mut value = match c {
+      b'0'..=b'9' => u32::from(c - b'0'),
+      b'A'..=b'Z' => u32::from(c - b'A') + 10,
+      b'*' => 36,
+      b'@' => 37,
+      b'#' => 38,
+      _ => return None,
+    };
+    if i % 2 == 1 {
+      value *= 2;
+    }
+    sum += value / 10 + value % 10;
+  }
+
+  Some(b'0' + ((10 - sum % 10) % 10) as u8)
+}
+
+fn is_consonant(c: u8) -> bool {
+  c.is_ascii_uppercase() && !b"AEIOU".contains(&c)
+}
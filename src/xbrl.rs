@@ -3,11 +3,13 @@ use napi_derive::napi;
 use roxmltree::{Document as XMLDoc, Node};
 use std::{collections::HashMap, rc::Rc};
 
-use crate::{parse_date, parse_value};
+use crate::error::ParseError;
+use crate::{parse_date, parse_value, Diagnostic, Diagnostics, ParseOptions};
 
 #[napi(object)]
 pub struct XBRL {
   pub facts: Vec<Fact>,
+  pub warnings: Vec<Diagnostic>,
 }
 
 #[napi(object)]
@@ -22,8 +24,17 @@ pub struct Fact {
 #[napi(object)]
 #[derive(Clone)]
 pub struct Context {
+  /// Canonical key built from the entity, period, sorted dimensions and sorted non-dimensional
+  /// segment and scenario content, stable across filings.
+  pub key: String,
   pub entity: Rc<String>,
+  pub entity_scheme: Option<String>,
   pub segments: Vec<Segment>,
+  /// Raw XML of segment children that are not dimension members.
+  pub segment_content: Vec<String>,
+  pub scenarios: Vec<Segment>,
+  /// Raw XML of scenario children that are not dimension members.
+  pub scenario_content: Vec<String>,
   pub period: Rc<Period>,
 }
 
@@ -43,8 +54,8 @@ pub struct Period {
 }
 
 #[napi]
-pub fn parse_xbrl(env: Env, xbrl: String) -> Result<XBRL, Error> {
-  let doc = XMLDoc::parse(&xbrl).map_err(|e| Error::from_reason(e.to_string()))?;
+pub fn parse_xbrl(env: Env, xbrl: String, options: Option<ParseOptions>) -> Result<XBRL, Error> {
+  let doc = XMLDoc::parse(&xbrl).map_err(|e| ParseError::from(e).into_napi(env))?;
   let root = doc.root_element();
 
   let xbrldi_ns = root
@@ -52,38 +63,51 @@ pub fn parse_xbrl(env: Env, xbrl: String) -> Result<XBRL, Error> {
     .find(|ns| ns.name() == Some("xbrldi"))
     .map_or_else(String::new, |ns| ns.uri().to_owned());
 
+  let diagnostics = &Diagnostics::new(ParseOptions::mode(&options));
+
   let units = parse_units(&root);
-  let contexts = parse_contexts(&root, &xbrldi_ns);
+  let contexts = parse_contexts(&root, &xbrldi_ns, diagnostics).map_err(|e| e.into_napi(env))?;
 
-  let facts: Result<Vec<_>, Error> = root
-    .children()
-    .filter_map(|node| {
-      node.attribute("contextRef").and_then(|context_ref| {
-        contexts.get(context_ref).map(|context| {
-          let concept = node.tag_name().name().to_owned();
-          let value_str = node.text().unwrap_or_default().to_owned();
-          let value = parse_value(env, &value_str)?;
-          let decimals = node.attribute("decimals").map(|s| s.to_owned());
-          let unit = if let Some(unit_ref) = node.attribute("unitRef") {
-            units.get(unit_ref).cloned()
-          } else {
-            None
-          };
-          Ok(Fact {
-            context: Rc::new(context.clone()),
-            concept,
-            value,
-            decimals,
-            unit,
-          })
-        })
-      })
-    })
-    .collect();
+  let mut facts = vec![];
+  for node in root.children() {
+    let Some(context_ref) = node.attribute("contextRef") else {
+      continue;
+    };
+    let context = match contexts.get(context_ref) {
+      Some(Some(context)) => context,
+      // The context was dropped with its own diagnostic.
+      Some(None) => continue,
+      None => {
+        let err = ParseError::missing(&root, &format!("context[@id='{}']", context_ref));
+        diagnostics
+          .skip_row(&node, err)
+          .map_err(|e| e.into_napi(env))?;
+        continue;
+      }
+    };
 
-  let facts = facts?;
+    let concept = node.tag_name().name().to_owned();
+    let value_str = node.text().unwrap_or_default().to_owned();
+    let value = parse_value(env, &value_str)?;
+    let decimals = node.attribute("decimals").map(|s| s.to_owned());
+    let unit = if let Some(unit_ref) = node.attribute("unitRef") {
+      units.get(unit_ref).cloned()
+    } else {
+      None
+    };
+    facts.push(Fact {
+      context: Rc::new(context.clone()),
+      concept,
+      value,
+      decimals,
+      unit,
+    });
+  }
 
-  Ok(XBRL { facts })
+  Ok(XBRL {
+    facts,
+    warnings: diagnostics.take_warnings(),
+  })
 }
 
 fn parse_units(root: &Node) -> HashMap<String, String> {
@@ -124,66 +148,187 @@ fn parse_units(root: &Node) -> HashMap<String, String> {
   units
 }
 
-fn parse_contexts(root: &Node, xbrldi_ns: &str) -> HashMap<String, Context> {
+/// Parses contexts by id. Contexts that fail to parse in lenient mode map to `None`.
+fn parse_contexts(
+  root: &Node,
+  xbrldi_ns: &str,
+  diagnostics: &Diagnostics,
+) -> Result<HashMap<String, Option<Context>>, ParseError> {
   let mut contexts = HashMap::new();
 
   for context_node in root.children().filter(|node| node.has_tag_name("context")) {
-    let context_id = context_node.attribute("id").unwrap().to_owned();
-
-    if let Some(entity_node) = context_node
-      .children()
-      .find(|node| node.has_tag_name("entity"))
-    {
-      let entity = entity_node
-        .children()
-        .find(|node| node.has_tag_name("identifier"))
-        .and_then(|node| node.text())
-        .unwrap_or("");
-
-      let mut segments = vec![];
-      for segment_node in entity_node
-        .children()
-        .filter(|node| node.has_tag_name("segment"))
-      {
-        for member_node in segment_node
-          .children()
-          .filter(|node| node.has_tag_name((xbrldi_ns, "explicitMember")))
-        {
-          let raw_dimension = member_node.attribute("dimension").unwrap().to_owned();
-          let dimension = raw_dimension.split(':').nth(1).unwrap_or("");
-          let raw_member = member_node.text().unwrap_or_default().to_owned();
-          let member = raw_member.split(':').nth(1).unwrap_or("");
-
-          segments.push(Segment {
-            dimension: Rc::new(dimension.to_owned()),
-            member: Rc::new(member.to_owned()),
-          });
+    match parse_context(&context_node, xbrldi_ns) {
+      Ok((context_id, context)) => {
+        contexts.insert(context_id, Some(context));
+      }
+      Err(err) => {
+        diagnostics.recover(err)?;
+        if let Some(context_id) = context_node.attribute("id") {
+          contexts.insert(context_id.to_owned(), None);
         }
       }
+    }
+  }
+
+  Ok(contexts)
+}
+
+fn parse_context(context_node: &Node, xbrldi_ns: &str) -> Result<(String, Context), ParseError> {
+  let context_id = context_node
+    .attribute("id")
+    .ok_or_else(|| ParseError::missing_attribute(context_node, "id"))?
+    .to_owned();
+
+  let entity_node = context_node
+    .children()
+    .find(|node| node.has_tag_name("entity"))
+    .ok_or_else(|| ParseError::missing(context_node, "entity"))?;
+
+  let identifier_node = entity_node
+    .children()
+    .find(|node| node.has_tag_name("identifier"));
+  let entity = identifier_node
+    .and_then(|node| node.text())
+    .unwrap_or("")
+    .trim();
+  let entity_scheme = identifier_node
+    .and_then(|node| node.attribute("scheme"))
+    .map(|s| s.to_owned());
+
+  let (segments, segment_content) = parse_context_members(&entity_node, "segment", xbrldi_ns)?;
+  let (scenarios, scenario_content) = parse_context_members(context_node, "scenario", xbrldi_ns)?;
+
+  let period_node = context_node
+    .children()
+    .find(|node| node.has_tag_name("period"))
+    .ok_or_else(|| ParseError::missing(context_node, "period"))?;
+
+  let period = Period {
+    instant: parse_date(&period_node, "instant"),
+    start_date: parse_date(&period_node, "startDate"),
+    end_date: parse_date(&period_node, "endDate"),
+  };
+  let key = context_key(
+    entity_scheme.as_deref(),
+    entity,
+    &period,
+    segments.iter().chain(scenarios.iter()),
+    &segment_content,
+    &scenario_content,
+  );
 
-      if let Some(period_node) = context_node
-        .children()
-        .find(|node| node.has_tag_name("period"))
-      {
-        let period = Period {
-          instant: parse_date(&period_node, "instant"),
-          start_date: parse_date(&period_node, "startDate"),
-          end_date: parse_date(&period_node, "endDate"),
-        };
-
-        contexts.insert(
-          context_id,
-          Context {
-            entity: Rc::new(entity.to_owned()),
-            segments,
-            period: Rc::new(period),
-          },
-        );
+  Ok((
+    context_id,
+    Context {
+      key,
+      entity: Rc::new(entity.to_owned()),
+      entity_scheme,
+      segments,
+      segment_content,
+      scenarios,
+      scenario_content,
+      period: Rc::new(period),
+    },
+  ))
+}
+
+fn parse_context_members(
+  node: &Node,
+  container: &str,
+  xbrldi_ns: &str,
+) -> Result<(Vec<Segment>, Vec<String>), ParseError> {
+  let mut members = vec![];
+  let mut content = vec![];
+
+  for container_node in node.children().filter(|node| node.has_tag_name(container)) {
+    for child_node in container_node.children().filter(|node| node.is_element()) {
+      if child_node.has_tag_name((xbrldi_ns, "explicitMember")) {
+        let raw_dimension = child_node
+          .attribute("dimension")
+          .ok_or_else(|| ParseError::missing_attribute(&child_node, "dimension"))?;
+        let dimension = raw_dimension.split(':').nth(1).unwrap_or("");
+        let raw_member = child_node.text().unwrap_or_default().to_owned();
+        let member = raw_member.split(':').nth(1).unwrap_or("");
+
+        members.push(Segment {
+          dimension: Rc::new(dimension.to_owned()),
+          member: Rc::new(member.to_owned()),
+        });
+      } else if child_node.has_tag_name((xbrldi_ns, "typedMember")) {
+        let raw_dimension = child_node
+          .attribute("dimension")
+          .ok_or_else(|| ParseError::missing_attribute(&child_node, "dimension"))?;
+        let dimension = raw_dimension.split(':').nth(1).unwrap_or("");
+        let member = get_text_or_default(child_node.children().find(|node| node.is_element()));
+
+        members.push(Segment {
+          dimension: Rc::new(dimension.to_owned()),
+          member: Rc::new(member.trim().to_owned()),
+        });
+      } else {
+        let input = child_node.document().input_text();
+        content.push(input[child_node.range()].to_owned());
       }
     }
   }
 
-  contexts
+  Ok((members, content))
+}
+
+fn context_key<'a>(
+  scheme: Option<&str>,
+  entity: &str,
+  period: &Period,
+  members: impl Iterator<Item = &'a Segment>,
+  segment_content: &[String],
+  scenario_content: &[String],
+) -> String {
+  let period_key = match (&period.instant, &period.start_date, &period.end_date) {
+    (Some(instant), _, _) => instant.trim().to_owned(),
+    (None, Some(start_date), Some(end_date)) => {
+      format!("{}--{}", start_date.trim(), end_date.trim())
+    }
+    _ => "forever".to_owned(),
+  };
+
+  let mut dimensions: Vec<String> = members
+    .map(|member| format!("{}={}", member.dimension, member.member))
+    .collect();
+  dimensions.sort();
+
+  let mut key = format!(
+    "{}#{}|{}|{}",
+    scheme.unwrap_or_default(),
+    entity,
+    period_key,
+    dimensions.join(",")
+  );
+
+  // Whitespace between and inside elements does not change the content.
+  let mut content: Vec<String> = segment_content
+    .iter()
+    .map(|xml| format!("segment:{}", normalize_xml(xml)))
+    .chain(
+      scenario_content
+        .iter()
+        .map(|xml| format!("scenario:{}", normalize_xml(xml))),
+    )
+    .collect();
+  content.sort();
+  if !content.is_empty() {
+    key.push('|');
+    key.push_str(&content.join(","));
+  }
+
+  key
+}
+
+fn normalize_xml(xml: &str) -> String {
+  xml
+    .split_whitespace()
+    .collect::<Vec<_>>()
+    .join(" ")
+    .replace("> <", "><")
 }
 
 fn get_text_or_default(node: Option<Node>) -> String {